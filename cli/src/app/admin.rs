@@ -7,6 +7,7 @@ pub fn app() -> Command<'static> {
         .subcommand(admin_cancel_app())
         .subcommand(admin_health_app())
         .subcommand(admin_initialize_app())
+        .subcommand(admin_network_app())
 }
 
 fn admin_cancel_app() -> Command<'static> {
@@ -27,5 +28,78 @@ fn admin_health_app() -> Command<'static> {
 }
 
 fn admin_initialize_app() -> Command<'static> {
-    Command::new("initialize").about("Initializes the Cronos program")
+    Command::new("initialize")
+        .about("Initializes the Cronos program")
+        .arg(
+            Arg::new("daemon_lamports")
+                .long("daemon_lamports")
+                .takes_value(true)
+                .default_value("1000000000")
+                .help("Lamports to fund the scheduler's daemon with, for the snapshot task's fees"),
+        )
+}
+
+fn admin_network_app() -> Command<'static> {
+    Command::new("network")
+        .about("Admin network commands")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("config")
+                .about("Manage the network config")
+                .subcommand_required(true)
+                .subcommand(admin_network_config_set_app()),
+        )
+}
+
+fn admin_network_config_set_app() -> Command<'static> {
+    Command::new("set")
+        .about("Set network config variables, keeping the current values of any omitted")
+        .arg(
+            Arg::new("admin")
+                .long("admin")
+                .takes_value(true)
+                .help("The new admin authority"),
+        )
+        .arg(
+            Arg::new("crank_authority")
+                .long("crank_authority")
+                .takes_value(true)
+                .help("The new authority allowed to start network snapshots"),
+        )
+        .arg(
+            Arg::new("heartbeat_window")
+                .long("heartbeat_window")
+                .takes_value(true)
+                .help("The new number of seconds a node may go without a heartbeat and join the pool, or 0 to disable"),
+        )
+        .arg(
+            Arg::new("miss_window")
+                .long("miss_window")
+                .takes_value(true)
+                .help("The new number of seconds delegates have to execute a due task"),
+        )
+        .arg(
+            Arg::new("pool_size")
+                .long("pool_size")
+                .takes_value(true)
+                .help("The new number of delegates in the pool"),
+        )
+        .arg(
+            Arg::new("slash_rate")
+                .long("slash_rate")
+                .takes_value(true)
+                .help("The new fraction of a node's tokens slashed, in basis points"),
+        )
+        .arg(
+            Arg::new("slash_threshold")
+                .long("slash_threshold")
+                .takes_value(true)
+                .help("The new number of missed executions which slash a node"),
+        )
+        .arg(
+            Arg::new("unbonding_period")
+                .long("unbonding_period")
+                .takes_value(true)
+                .help("The new unbonding period, in seconds"),
+        )
 }
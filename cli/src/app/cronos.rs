@@ -11,5 +11,6 @@ pub fn cronos() -> Command<'static> {
         .subcommand(super::config::app())
         .subcommand(super::daemon::app())
         .subcommand(super::health::app())
+        .subcommand(super::node::app())
         .subcommand(super::task::app())
 }
@@ -4,6 +4,7 @@ mod config;
 mod cronos;
 mod daemon;
 mod health;
+mod node;
 mod task;
 
 pub use cronos::*;
@@ -0,0 +1,30 @@
+use clap::{Arg, Command};
+
+pub fn app() -> Command<'static> {
+    Command::new("node")
+        .about("Manage your worker node")
+        .subcommand(Command::new("claim").about("Claim the node's share of the network's rewards"))
+        .subcommand(Command::new("register").about("Register a node with the Cronos network"))
+        .subcommand(
+            Command::new("update")
+                .about("Update the node's metadata, clearing any omitted fields")
+                .arg(
+                    Arg::new("name")
+                        .long("name")
+                        .takes_value(true)
+                        .help("A display name for the node"),
+                )
+                .arg(
+                    Arg::new("plugin_version")
+                        .long("plugin_version")
+                        .takes_value(true)
+                        .help("The version of the Cronos plugin the node runs"),
+                )
+                .arg(
+                    Arg::new("url")
+                        .long("url")
+                        .takes_value(true)
+                        .help("A URL with more information about the node"),
+                ),
+        )
+}
@@ -1,4 +1,5 @@
 use clap::ArgMatches;
+use cronos_sdk::network::state::NodeMetadata;
 use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
 use std::{convert::TryFrom, fmt::Display};
 
@@ -8,28 +9,51 @@ use crate::{error::CliError, parser::*};
 pub enum CliCommand {
     AdminHealthReset,
     AdminTaskCancel { address: Pubkey },
-    AdminInitialize,
+    AdminInitialize { daemon_lamports: u64 },
+    AdminNetworkConfigSet { changes: NetworkConfigChanges },
     ClockGet,
     ConfigGet,
     DaemonGet,
     DaemonNew,
     HealthGet,
+    NodeClaim,
+    NodeGet,
+    NodeRegister,
+    NodeUpdate { metadata: NodeMetadata },
     TaskCancel { address: Pubkey },
     TaskGet { address: Pubkey },
     TaskNew { ix: Instruction, schedule: String },
 }
 
+/// Network config settings to change. Omitted settings keep their current values.
+#[derive(Debug, PartialEq)]
+pub struct NetworkConfigChanges {
+    pub admin: Option<Pubkey>,
+    pub crank_authority: Option<Pubkey>,
+    pub heartbeat_window: Option<i64>,
+    pub miss_window: Option<i64>,
+    pub pool_size: Option<u64>,
+    pub slash_rate: Option<u64>,
+    pub slash_threshold: Option<u64>,
+    pub unbonding_period: Option<i64>,
+}
+
 impl Display for CliCommand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CliCommand::AdminHealthReset => write!(f, "admin health reset"),
-            CliCommand::AdminInitialize => write!(f, "admin initialize"),
+            CliCommand::AdminInitialize { .. } => write!(f, "admin initialize"),
+            CliCommand::AdminNetworkConfigSet { .. } => write!(f, "admin network config set"),
             CliCommand::AdminTaskCancel { address } => write!(f, "admin task cancel {}", address),
             CliCommand::ClockGet => write!(f, "clock"),
             CliCommand::ConfigGet => write!(f, "config"),
             CliCommand::DaemonGet => write!(f, "daemon"),
             CliCommand::DaemonNew => write!(f, "daemon new"),
             CliCommand::HealthGet => write!(f, "health"),
+            CliCommand::NodeClaim => write!(f, "node claim"),
+            CliCommand::NodeGet => write!(f, "node"),
+            CliCommand::NodeRegister => write!(f, "node register"),
+            CliCommand::NodeUpdate { .. } => write!(f, "node update"),
             CliCommand::TaskCancel { address } => write!(f, "task cancel {}", address),
             CliCommand::TaskGet { address } => write!(f, "task {}", address),
             CliCommand::TaskNew { .. } => write!(f, "task new"),
@@ -47,6 +71,7 @@ impl TryFrom<&ArgMatches> for CliCommand {
             Some(("config", matches)) => config_command(matches),
             Some(("daemon", matches)) => daemon_command(matches),
             Some(("health", matches)) => health_command(matches),
+            Some(("node", matches)) => node_command(matches),
             Some(("task", matches)) => task_command(matches),
             _ => Err(CliError::CommandNotRecognized(
                 matches.subcommand().unwrap().0.into(),
@@ -1,13 +1,21 @@
 use clap::ArgMatches;
 
-use super::utils::parse_pubkey;
-use crate::{command::CliCommand, error::CliError};
+use super::utils::{
+    parse_i64_optional, parse_pubkey, parse_pubkey_optional, parse_u64, parse_u64_optional,
+};
+use crate::{
+    command::{CliCommand, NetworkConfigChanges},
+    error::CliError,
+};
 
 pub fn admin_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     match matches.subcommand() {
         Some(("task", matches)) => admin_task_command(matches),
         Some(("health", matches)) => admin_health_command(matches),
-        Some(("initialize", _matches)) => Ok(CliCommand::AdminInitialize),
+        Some(("initialize", matches)) => Ok(CliCommand::AdminInitialize {
+            daemon_lamports: parse_u64(&"daemon_lamports".into(), matches)?,
+        }),
+        Some(("network", matches)) => admin_network_command(matches),
         _ => Err(CliError::CommandNotRecognized(
             matches.subcommand().unwrap().0.into(),
         )),
@@ -23,6 +31,35 @@ fn admin_health_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     }
 }
 
+fn admin_network_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
+    match matches.subcommand() {
+        Some(("config", matches)) => admin_network_config_command(matches),
+        _ => Err(CliError::CommandNotRecognized(
+            matches.subcommand().unwrap().0.into(),
+        )),
+    }
+}
+
+fn admin_network_config_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
+    match matches.subcommand() {
+        Some(("set", matches)) => Ok(CliCommand::AdminNetworkConfigSet {
+            changes: NetworkConfigChanges {
+                admin: parse_pubkey_optional(&"admin".into(), matches)?,
+                crank_authority: parse_pubkey_optional(&"crank_authority".into(), matches)?,
+                heartbeat_window: parse_i64_optional(&"heartbeat_window".into(), matches)?,
+                miss_window: parse_i64_optional(&"miss_window".into(), matches)?,
+                pool_size: parse_u64_optional(&"pool_size".into(), matches)?,
+                slash_rate: parse_u64_optional(&"slash_rate".into(), matches)?,
+                slash_threshold: parse_u64_optional(&"slash_threshold".into(), matches)?,
+                unbonding_period: parse_i64_optional(&"unbonding_period".into(), matches)?,
+            },
+        }),
+        _ => Err(CliError::CommandNotRecognized(
+            matches.subcommand().unwrap().0.into(),
+        )),
+    }
+}
+
 fn admin_task_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     match matches.subcommand() {
         Some(("cancel", matches)) => Ok(CliCommand::AdminTaskCancel {
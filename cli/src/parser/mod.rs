@@ -2,6 +2,7 @@ mod admin_command;
 mod config_command;
 mod daemon_command;
 mod health_command;
+mod node_command;
 mod task_command;
 mod utils;
 
@@ -9,4 +10,5 @@ pub use admin_command::*;
 pub use config_command::*;
 pub use daemon_command::*;
 pub use health_command::*;
+pub use node_command::*;
 pub use task_command::*;
@@ -0,0 +1,20 @@
+use clap::ArgMatches;
+use cronos_sdk::network::state::NodeMetadata;
+
+use super::utils::parse_string_optional;
+use crate::{command::CliCommand, error::CliError};
+
+pub fn node_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
+    match matches.subcommand() {
+        Some(("claim", _matches)) => Ok(CliCommand::NodeClaim {}),
+        Some(("register", _matches)) => Ok(CliCommand::NodeRegister {}),
+        Some(("update", matches)) => Ok(CliCommand::NodeUpdate {
+            metadata: NodeMetadata {
+                name: parse_string_optional(&"name".into(), matches)?,
+                plugin_version: parse_string_optional(&"plugin_version".into(), matches)?,
+                url: parse_string_optional(&"url".into(), matches)?,
+            },
+        }),
+        _ => Ok(CliCommand::NodeGet {}),
+    }
+}
@@ -6,32 +6,47 @@ use solana_sdk::{instruction::{AccountMeta, Instruction}, pubkey::Pubkey};
 
 use crate::error::CliError;
 
-pub fn _parse_i64(arg: &String, matches: &ArgMatches) -> Result<i64, CliError> {
+pub fn parse_i64(arg: &String, matches: &ArgMatches) -> Result<i64, CliError> {
     parse_string(arg, matches)?
         .parse::<i64>()
         .map_err(|_err| CliError::BadParameter(arg.into()))
 }
 
-pub fn _parse_i64_optional(arg: &String, matches: &ArgMatches) -> Result<Option<i64>, CliError> {
-    let i = _parse_i64(arg, matches);
-    if i.is_err() {
-        Ok(None)
-    } else {
-        Ok(i.ok())
+pub fn parse_i64_optional(arg: &String, matches: &ArgMatches) -> Result<Option<i64>, CliError> {
+    match matches.value_of(arg) {
+        Some(_) => Ok(Some(parse_i64(arg, matches)?)),
+        None => Ok(None),
     }
 }
 
-pub fn _parse_u64(arg: &String, matches: &ArgMatches) -> Result<u64, CliError> {
+pub fn parse_u64(arg: &String, matches: &ArgMatches) -> Result<u64, CliError> {
     parse_string(arg, matches)?
         .parse::<u64>()
         .map_err(|_err| CliError::BadParameter(arg.into()))
 }
 
+pub fn parse_u64_optional(arg: &String, matches: &ArgMatches) -> Result<Option<u64>, CliError> {
+    match matches.value_of(arg) {
+        Some(_) => Ok(Some(parse_u64(arg, matches)?)),
+        None => Ok(None),
+    }
+}
+
 pub fn parse_pubkey(arg: &String, matches: &ArgMatches) -> Result<Pubkey, CliError> {
     Ok(Pubkey::from_str(parse_string(arg, matches)?.as_str())
         .map_err(|_err| CliError::BadParameter(arg.into()))?)
 }
 
+pub fn parse_pubkey_optional(
+    arg: &String,
+    matches: &ArgMatches,
+) -> Result<Option<Pubkey>, CliError> {
+    match matches.value_of(arg) {
+        Some(_) => Ok(Some(parse_pubkey(arg, matches)?)),
+        None => Ok(None),
+    }
+}
+
 pub fn parse_string(arg: &String, matches: &ArgMatches) -> Result<String, CliError> {
     Ok(matches
         .value_of(arg)
@@ -39,6 +54,13 @@ pub fn parse_string(arg: &String, matches: &ArgMatches) -> Result<String, CliErr
         .to_string())
 }
 
+pub fn parse_string_optional(
+    arg: &String,
+    matches: &ArgMatches,
+) -> Result<Option<String>, CliError> {
+    Ok(matches.value_of(arg).map(|value| value.to_string()))
+}
+
 #[derive(Debug, JsonDeserialize, JsonSerialize)]
 pub struct JsonInstructionData {
     pub program_id: String,
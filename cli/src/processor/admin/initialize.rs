@@ -1,21 +1,109 @@
 use std::sync::Arc;
 
+use cronos_sdk::scheduler::state::Daemon;
 use solana_client_helpers::Client;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    system_instruction,
+};
 
-use crate::{error::CliError, utils::sign_and_submit};
+use crate::{
+    error::CliError,
+    utils::{sign_and_submit, sign_and_submit_with_signers},
+};
 
-pub fn initialize(client: &Arc<Client>) -> Result<(), CliError> {
+/// Schedule of the admin task which starts network snapshots, at the top of every hour.
+const SNAPSHOT_SCHEDULE: &str = "0 0 * * * *";
+
+/// Schedule of the admin task which commits the network pool's next draw, every minute.
+const POOL_COMMIT_SCHEDULE: &str = "0 * * * * *";
+
+/// Bootstrap the scheduler and the network.
+///
+/// The scheduler's authority daemon becomes the network's crank authority, and an admin task
+/// signed by that daemon starts a new snapshot every hour. Once a snapshot is started, nodes
+/// crank it to completion. Another admin task commits a pool draw every minute, which nodes
+/// cycle into the pool from the current snapshot. The daemon pays the automation fees of the
+/// tasks, so it's funded with `daemon_lamports` from the admin.
+///
+/// Programs that are already initialized are skipped, so a network can be bootstrapped next to
+/// a deployed scheduler. Until the network exists, any node may execute tasks.
+pub fn initialize(client: &Arc<Client>, daemon_lamports: u64) -> Result<(), CliError> {
+    let admin = client.payer_pubkey();
+
+    // Initialize the scheduler, or migrate a config deployed before its latest fields
     let authority_pda = cronos_sdk::scheduler::state::Authority::pda();
     let config_pda = cronos_sdk::scheduler::state::Config::pda();
     let daemon_pda = cronos_sdk::scheduler::state::Daemon::pda(authority_pda.0);
     let fee_pda = cronos_sdk::scheduler::state::Fee::pda(daemon_pda.0);
-    let ix = cronos_sdk::scheduler::instruction::admin_initialize(
-        client.payer_pubkey(),
-        authority_pda,
-        config_pda,
-        daemon_pda,
-        fee_pda,
+    let config = client
+        .get_account_data(&config_pda.0)
+        .ok()
+        .map(cronos_sdk::scheduler::state::Config::try_from);
+    match config {
+        None => {
+            let ix = cronos_sdk::scheduler::instruction::admin_initialize(
+                admin,
+                authority_pda,
+                config_pda,
+                daemon_pda,
+                fee_pda,
+            );
+            sign_and_submit(client, &[ix]);
+        }
+        Some(Err(_)) => {
+            let ix = cronos_sdk::scheduler::instruction::admin_config_migrate(admin, config_pda.0);
+            sign_and_submit(client, &[ix]);
+        }
+        Some(Ok(_)) => println!("The scheduler is already initialized"),
+    }
+
+    // Initialize the network with the scheduler's daemon as the crank authority
+    let network_config_pda = cronos_sdk::network::state::Config::pda();
+    if client.get_account_data(&network_config_pda.0).is_ok() {
+        println!("The network is already initialized");
+        return Ok(());
+    }
+    let mint = Keypair::new();
+    let ix = cronos_sdk::network::instruction::initialize(admin, daemon_pda.0, mint.pubkey());
+    sign_and_submit_with_signers(client, &[ix], &[&mint]);
+
+    // Fund the daemon and install the admin tasks which start network snapshots and commit
+    // pool draws
+    let daemon_data = client
+        .get_account_data(&daemon_pda.0)
+        .map_err(|_err| CliError::AccountNotFound(daemon_pda.0.to_string()))?;
+    let daemon = Daemon::try_from(daemon_data)
+        .map_err(|_err| CliError::AccountDataNotParsable(daemon_pda.0.to_string()))?;
+    let fund_ix = system_instruction::transfer(&admin, &daemon_pda.0, daemon_lamports);
+    let snapshot_start_ix = cronos_sdk::network::instruction::snapshot_start(
+        daemon_pda.0,
+        network_config_pda.0,
+        cronos_sdk::network::state::Registry::pda().0,
+    );
+    let snapshot_task_ix = cronos_sdk::scheduler::instruction::admin_task_new(
+        cronos_sdk::scheduler::state::Task::pda(daemon_pda.0, daemon.task_count),
+        admin,
+        authority_pda.0,
+        config_pda.0,
+        daemon_pda.0,
+        vec![snapshot_start_ix],
+        SNAPSHOT_SCHEDULE.into(),
+    );
+    let pool_commit_ix = cronos_sdk::network::instruction::pool_commit(
+        daemon_pda.0,
+        network_config_pda.0,
+        cronos_sdk::network::state::Pool::pda().0,
+    );
+    let pool_task_ix = cronos_sdk::scheduler::instruction::admin_task_new(
+        cronos_sdk::scheduler::state::Task::pda(daemon_pda.0, daemon.task_count + 1),
+        admin,
+        authority_pda.0,
+        config_pda.0,
+        daemon_pda.0,
+        vec![pool_commit_ix],
+        POOL_COMMIT_SCHEDULE.into(),
     );
-    sign_and_submit(client, &[ix]);
+    sign_and_submit(client, &[fund_ix, snapshot_task_ix, pool_task_ix]);
     Ok(())
 }
@@ -1,7 +1,9 @@
 mod health_reset;
 mod initialize;
+mod network_config_set;
 mod task_cancel;
 
 pub use health_reset::*;
 pub use initialize::*;
+pub use network_config_set::*;
 pub use task_cancel::*;
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use cronos_sdk::network::state::{Config, ConfigSettings};
+use solana_client_helpers::Client;
+
+use crate::{command::NetworkConfigChanges, error::CliError, utils::sign_and_submit};
+
+pub fn network_config_set(
+    client: &Arc<Client>,
+    changes: &NetworkConfigChanges,
+) -> Result<(), CliError> {
+    // Fetch the current config, so omitted settings keep their values
+    let config_addr = Config::pda().0;
+    let data = client
+        .get_account_data(&config_addr)
+        .map_err(|_err| CliError::AccountNotFound(config_addr.to_string()))?;
+    let config = Config::try_from(data)
+        .map_err(|_err| CliError::AccountDataNotParsable(config_addr.to_string()))?;
+
+    let settings = ConfigSettings {
+        admin: changes.admin.unwrap_or(config.admin),
+        crank_authority: changes.crank_authority.unwrap_or(config.crank_authority),
+        heartbeat_window: changes.heartbeat_window.unwrap_or(config.heartbeat_window),
+        miss_window: changes.miss_window.unwrap_or(config.miss_window),
+        pool_size: changes.pool_size.unwrap_or(config.pool_size),
+        slash_rate: changes.slash_rate.unwrap_or(config.slash_rate),
+        slash_threshold: changes.slash_threshold.unwrap_or(config.slash_threshold),
+        unbonding_period: changes.unbonding_period.unwrap_or(config.unbonding_period),
+    };
+    let ix = cronos_sdk::network::instruction::admin_config_update(
+        client.payer_pubkey(),
+        config_addr,
+        settings,
+    );
+    sign_and_submit(client, &[ix]);
+    Ok(())
+}
@@ -3,6 +3,7 @@ mod clock;
 mod config;
 mod daemon;
 mod health;
+mod node;
 mod process;
 mod task;
 
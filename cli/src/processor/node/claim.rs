@@ -0,0 +1,76 @@
+use {
+    crate::{error::CliError, utils::sign_and_submit},
+    cronos_sdk::network::state::{
+        first_claimable_snapshot_id, Node, Registry, Rewards, Snapshot, SnapshotPage, PAGE_LIMIT,
+    },
+    solana_client_helpers::Client,
+    std::sync::Arc,
+};
+
+pub fn claim(client: &Arc<Client>) -> Result<(), CliError> {
+    let authority = client.payer_pubkey();
+
+    // Fetch the node and the registry
+    let node_addr = Node::pda(authority).0;
+    let node_data = client
+        .get_account_data(&node_addr)
+        .map_err(|_err| CliError::AccountNotFound(node_addr.to_string()))?;
+    let node = Node::try_from(node_data)
+        .map_err(|_err| CliError::AccountDataNotParsable(node_addr.to_string()))?;
+    let registry_addr = Registry::pda().0;
+    let registry_data = client
+        .get_account_data(&registry_addr)
+        .map_err(|_err| CliError::AccountNotFound(registry_addr.to_string()))?;
+    let registry = Registry::try_from(registry_data)
+        .map_err(|_err| CliError::AccountDataNotParsable(registry_addr.to_string()))?;
+
+    // Claim the rewards of each archived snapshot the node hasn't claimed yet, in order. The last
+    // snapshot is current, so its rewards aren't final. Snapshots past their claim window are
+    // skipped, since their rewards were released.
+    let first_id = node
+        .claimable_snapshot_id
+        .max(first_claimable_snapshot_id(registry.snapshot_count));
+    let archived_count = registry.snapshot_count.saturating_sub(1);
+    for id in first_id..archived_count {
+        let snapshot_addr = Snapshot::pda(id).0;
+        let snapshot_data = client
+            .get_account_data(&snapshot_addr)
+            .map_err(|_err| CliError::AccountNotFound(snapshot_addr.to_string()))?;
+        let snapshot = Snapshot::try_from(snapshot_data)
+            .map_err(|_err| CliError::AccountDataNotParsable(snapshot_addr.to_string()))?;
+
+        // Find the node's entry, which the claim reads from the single page holding it
+        let mut entry_id = None;
+        for page_id in 0..snapshot.page_count {
+            let snapshot_page_addr = SnapshotPage::pda(snapshot_addr, page_id).0;
+            let snapshot_page_data = client
+                .get_account_data(&snapshot_page_addr)
+                .map_err(|_err| CliError::AccountNotFound(snapshot_page_addr.to_string()))?;
+            let snapshot_page = SnapshotPage::try_from(snapshot_page_data)
+                .map_err(|_err| CliError::AccountDataNotParsable(snapshot_page_addr.to_string()))?;
+            if let Some(index) = snapshot_page
+                .entries
+                .iter()
+                .position(|entry| entry.node_authority == authority)
+            {
+                entry_id = Some(page_id * PAGE_LIMIT as u64 + index as u64);
+                break;
+            }
+        }
+        let entry_id = entry_id.ok_or(CliError::AccountNotFound(format!(
+            "node {} in snapshot {}",
+            node_addr, snapshot_addr
+        )))?;
+
+        let ix = cronos_sdk::network::instruction::node_claim_rewards(
+            authority,
+            entry_id,
+            node_addr,
+            registry_addr,
+            Rewards::pda().0,
+            snapshot_addr,
+        );
+        sign_and_submit(client, &[ix]);
+    }
+    super::get(client)
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use solana_client_helpers::Client;
+
+use crate::{
+    error::CliError,
+    utils::{solana_explorer_url, SolanaExplorerAccountType},
+};
+
+pub fn get(client: &Arc<Client>) -> Result<(), CliError> {
+    let authority = client.payer_pubkey();
+    let node_addr = cronos_sdk::network::state::Node::pda(authority).0;
+    let data = client
+        .get_account_data(&node_addr)
+        .map_err(|_err| CliError::AccountNotFound(node_addr.to_string()))?;
+    let node_data = cronos_sdk::network::state::Node::try_from(data)
+        .map_err(|_err| CliError::AccountDataNotParsable(node_addr.to_string()))?;
+    println!(
+        "Explorer: {}",
+        solana_explorer_url(SolanaExplorerAccountType::Account, node_addr.to_string())
+    );
+    println!("{:#?}", node_data);
+    Ok(())
+}
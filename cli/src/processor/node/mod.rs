@@ -0,0 +1,9 @@
+mod claim;
+mod get;
+mod register;
+mod update;
+
+pub use claim::*;
+pub use get::*;
+pub use register::*;
+pub use update::*;
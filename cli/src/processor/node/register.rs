@@ -0,0 +1,55 @@
+use {
+    crate::{error::CliError, utils::sign_and_submit},
+    cronos_sdk::network::state::{Node, Registry, RegistryPage, PAGE_LIMIT},
+    solana_client_helpers::Client,
+    std::sync::Arc,
+};
+
+pub fn register(client: &Arc<Client>) -> Result<(), CliError> {
+    let authority = client.payer_pubkey();
+
+    // Fetch the registry and its last page
+    let registry_addr = Registry::pda().0;
+    let registry_data = client
+        .get_account_data(&registry_addr)
+        .map_err(|_err| CliError::AccountNotFound(registry_addr.to_string()))?;
+    let registry = Registry::try_from(registry_data)
+        .map_err(|_err| CliError::AccountDataNotParsable(registry_addr.to_string()))?;
+    let registry_page_id = registry
+        .page_count
+        .checked_sub(1)
+        .ok_or_else(|| CliError::AccountDataNotParsable(registry_addr.to_string()))?;
+    let registry_page_addr = RegistryPage::pda(registry_page_id).0;
+    let registry_page_data = client
+        .get_account_data(&registry_page_addr)
+        .map_err(|_err| CliError::AccountNotFound(registry_page_addr.to_string()))?;
+    let registry_page = RegistryPage::try_from(registry_page_data)
+        .map_err(|_err| CliError::AccountDataNotParsable(registry_page_addr.to_string()))?;
+
+    // Open a new registry page if the last one is full
+    let mut ixs = vec![];
+    let registry_page_addr = if registry_page.nodes.len() >= PAGE_LIMIT {
+        let registry_page_pda = RegistryPage::pda(registry.page_count);
+        ixs.push(cronos_sdk::network::instruction::registry_page_new(
+            authority,
+            registry_addr,
+            registry_page_pda,
+        ));
+        registry_page_pda.0
+    } else {
+        registry_page_addr
+    };
+
+    // Register the node on the last page, along with a vault for its stake
+    let node_pda = Node::pda(authority);
+    ixs.push(cronos_sdk::network::instruction::node_register(
+        authority,
+        registry.mint,
+        node_pda,
+        registry_addr,
+        registry_page_addr,
+        Node::tokens_pda(node_pda.0).0,
+    ));
+    sign_and_submit(client, &ixs);
+    super::get(client)
+}
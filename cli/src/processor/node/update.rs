@@ -0,0 +1,14 @@
+use {
+    crate::{error::CliError, utils::sign_and_submit},
+    cronos_sdk::network::state::{Node, NodeMetadata},
+    solana_client_helpers::Client,
+    std::sync::Arc,
+};
+
+pub fn update(client: &Arc<Client>, metadata: NodeMetadata) -> Result<(), CliError> {
+    let authority = client.payer_pubkey();
+    let ix =
+        cronos_sdk::network::instruction::node_update(authority, Node::pda(authority).0, metadata);
+    sign_and_submit(client, &[ix]);
+    super::get(client)
+}
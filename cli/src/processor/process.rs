@@ -24,12 +24,21 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
     match command {
         CliCommand::AdminTaskCancel { address } => super::admin::task_cancel(&client, &address),
         CliCommand::AdminHealthReset => super::admin::health_reset(&client),
-        CliCommand::AdminInitialize => super::admin::initialize(&client),
+        CliCommand::AdminInitialize { daemon_lamports } => {
+            super::admin::initialize(&client, daemon_lamports)
+        }
+        CliCommand::AdminNetworkConfigSet { changes } => {
+            super::admin::network_config_set(&client, &changes)
+        }
         CliCommand::ClockGet => super::clock::get(&client),
         CliCommand::ConfigGet => super::config::get(&client),
         CliCommand::DaemonGet => super::daemon::get(&client),
         CliCommand::DaemonNew => super::daemon::new(&client),
         CliCommand::HealthGet => super::health::get(&client),
+        CliCommand::NodeClaim => super::node::claim(&client),
+        CliCommand::NodeGet => super::node::get(&client),
+        CliCommand::NodeRegister => super::node::register(&client),
+        CliCommand::NodeUpdate { metadata } => super::node::update(&client, metadata),
         CliCommand::TaskCancel { address } => super::task::cancel(&client, &address),
         CliCommand::TaskGet { address } => super::task::get(&client, &address),
         CliCommand::TaskNew { ix, schedule } => super::task::new(&client, ix, schedule),
@@ -16,8 +16,18 @@ pub fn load_keypair(config: &CliConfig) -> Keypair {
 }
 
 pub fn sign_and_submit(client: &Arc<Client>, ixs: &[Instruction]) {
+    sign_and_submit_with_signers(client, ixs, &[])
+}
+
+pub fn sign_and_submit_with_signers(
+    client: &Arc<Client>,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) {
     let mut tx = Transaction::new_with_payer(ixs, Some(&client.payer_pubkey()));
-    tx.sign(&vec![&client.payer], client.latest_blockhash().unwrap());
+    let mut all_signers = vec![&client.payer];
+    all_signers.extend_from_slice(signers);
+    tx.sign(&all_signers, client.latest_blockhash().unwrap());
     let sig = client.send_and_confirm_transaction(&tx).unwrap();
     println!(
         "Tx: {}",
@@ -0,0 +1,154 @@
+use {
+    cronos_plugin::{Executor, Settings},
+    dotenv::dotenv,
+    futures::StreamExt,
+    log::{debug, info, warn},
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{
+        nonblocking::pubsub_client::PubsubClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    },
+    solana_sdk::{account::Account, pubkey::Pubkey},
+    std::{env, process, str::FromStr, time::Duration},
+    tokio::{runtime::Handle, signal, task, time::sleep},
+};
+
+/// How long to wait before reconnecting a closed subscription.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[tokio::main]
+async fn main() {
+    dotenv().ok();
+
+    let config_path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "config.json".to_string());
+    let settings = Settings::load(&config_path).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1)
+    });
+    solana_logger::setup_with_default(settings.config.log_level());
+
+    info!("Starting node with config {}", config_path);
+    let executor = Executor::new(&config_path, settings, Handle::current()).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1)
+    });
+
+    // Tasks and daemons are owned by the scheduler, the pool by the network program
+    tokio::spawn(replicate_program(
+        executor.clone(),
+        cronos_sdk::SCHEDULER_PROGRAM_ID,
+    ));
+    tokio::spawn(replicate_program(
+        executor.clone(),
+        cronos_sdk::NETWORK_PROGRAM_ID,
+    ));
+    tokio::spawn(replicate_slots(executor.clone()));
+
+    if let Err(err) = signal::ctrl_c().await {
+        warn!("Failed to listen for ctrl-c: {}", err);
+    }
+
+    // Draining blocks, so keep it off the runtime's workers which confirm the submissions
+    info!("Shutting down");
+    task::spawn_blocking(move || executor.shutdown()).await.ok();
+}
+
+/// Replicate the accounts of a program, reconnecting whenever the subscription closes.
+async fn replicate_program(executor: Executor, program_id: Pubkey) {
+    loop {
+        let config = executor.config();
+        let ws_url = config.ws_url();
+        match PubsubClient::new(&ws_url).await {
+            Err(err) => warn!("Failed to connect to {}: {}", ws_url, err),
+            Ok(pubsub) => {
+                let subscription = pubsub
+                    .program_subscribe(
+                        &program_id,
+                        Some(RpcProgramAccountsConfig {
+                            account_config: RpcAccountInfoConfig {
+                                encoding: Some(UiAccountEncoding::Base64),
+                                commitment: Some(config.commitment()),
+                                ..RpcAccountInfoConfig::default()
+                            },
+                            ..RpcProgramAccountsConfig::default()
+                        }),
+                    )
+                    .await;
+                match subscription {
+                    Err(err) => warn!("Failed to subscribe to {}: {}", program_id, err),
+                    Ok((mut updates, _unsubscribe)) => {
+                        // Load the accounts that changed while there was no subscription
+                        load_program(&executor, &program_id).await;
+                        while let Some(update) = updates.next().await {
+                            let keyed_account = update.value;
+                            let key = Pubkey::from_str(&keyed_account.pubkey);
+                            match (key, keyed_account.account.decode::<Account>()) {
+                                (Ok(key), Some(account)) => {
+                                    replicate_account(&executor, key, &account)
+                                }
+                                _ => warn!("Failed to decode account {}", keyed_account.pubkey),
+                            }
+                        }
+                        warn!("Subscription to {} closed", program_id);
+                    }
+                }
+            }
+        }
+        if executor.is_shutting_down() {
+            return;
+        }
+        sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Advance cluster time with slot updates, reconnecting whenever the subscription closes.
+///
+/// Slot subscriptions report slots as they are processed, slightly ahead of the
+/// confirmed slots the plugin uses.
+async fn replicate_slots(executor: Executor) {
+    loop {
+        let ws_url = executor.config().ws_url();
+        match PubsubClient::new(&ws_url).await {
+            Err(err) => warn!("Failed to connect to {}: {}", ws_url, err),
+            Ok(pubsub) => match pubsub.slot_subscribe().await {
+                Err(err) => warn!("Failed to subscribe to slots: {}", err),
+                Ok((mut updates, _unsubscribe)) => {
+                    while let Some(slot_info) = updates.next().await {
+                        executor.update_slot(slot_info.slot);
+                    }
+                    warn!("Slot subscription closed");
+                }
+            },
+        }
+        if executor.is_shutting_down() {
+            return;
+        }
+        sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn load_program(executor: &Executor, program_id: &Pubkey) {
+    match executor
+        .client()
+        .client
+        .get_program_accounts(program_id)
+        .await
+    {
+        Ok(accounts) => {
+            info!("Loaded {} accounts of {}", accounts.len(), program_id);
+            for (key, account) in accounts {
+                replicate_account(executor, key, &account);
+            }
+        }
+        Err(err) => warn!("Failed to load the accounts of {}: {}", program_id, err),
+    }
+}
+
+fn replicate_account(executor: &Executor, key: Pubkey, account: &Account) {
+    if let Err(err) = executor.update_account(key, &account.owner, account.lamports, &account.data)
+    {
+        debug!("Skipped account {}: {}", key, err);
+    }
+}
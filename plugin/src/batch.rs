@@ -0,0 +1,146 @@
+use {
+    crate::budget::compute_budget_ixs,
+    solana_sdk::{
+        instruction::Instruction, message::Message, packet::PACKET_DATA_SIZE, pubkey::Pubkey,
+        transaction::Transaction,
+    },
+    std::collections::HashSet,
+};
+
+/// Maximum number of accounts a single transaction may lock.
+pub const MAX_TX_ACCOUNT_LOCKS: usize = 64;
+
+/// Maximum number of compute units a single transaction may consume.
+pub const MAX_TX_COMPUTE_UNITS: u64 = 1_400_000;
+
+/// Compute units assumed for a task_exec instruction when nothing better is known.
+pub const DEFAULT_TASK_COMPUTE_UNITS: u64 = 200_000;
+
+/// Greedily pack instructions into batches that fit in a single transaction.
+///
+/// A batch is closed when adding the next instruction would exceed `max_batch_size`,
+/// the account lock limit, the compute limit, or the packet size. Instructions that
+/// don't fit even on their own are still returned in a batch of one.
+pub fn pack<T>(
+    payer: &Pubkey,
+    items: Vec<(T, Instruction)>,
+    max_batch_size: usize,
+    compute_units: impl Fn(&T) -> u64,
+) -> Vec<Vec<(T, Instruction)>> {
+    let mut batches: Vec<Vec<(T, Instruction)>> = vec![];
+    let mut current: Vec<(T, Instruction)> = vec![];
+
+    for item in items {
+        current.push(item);
+        if current.len() > 1 && !fits(payer, &current, max_batch_size, &compute_units) {
+            let item = current.pop().unwrap();
+            batches.push(current);
+            current = vec![item];
+        }
+    }
+
+    if !current.is_empty() {
+        batches.push(current);
+    }
+
+    batches
+}
+
+fn fits<T>(
+    payer: &Pubkey,
+    items: &[(T, Instruction)],
+    max_batch_size: usize,
+    compute_units: &impl Fn(&T) -> u64,
+) -> bool {
+    if items.len() > max_batch_size {
+        return false;
+    }
+
+    // Check the compute budget
+    let compute_units = items
+        .iter()
+        .map(|(item, _)| compute_units(item))
+        .fold(0u64, |acc, units| acc.saturating_add(units));
+    if compute_units > MAX_TX_COMPUTE_UNITS {
+        return false;
+    }
+
+    // Check the account locks
+    let mut accounts = HashSet::new();
+    accounts.insert(*payer);
+    for (_, ix) in items {
+        accounts.insert(ix.program_id);
+        accounts.extend(ix.accounts.iter().map(|acc| acc.pubkey));
+    }
+    if accounts.len() > MAX_TX_ACCOUNT_LOCKS {
+        return false;
+    }
+
+    // Check the serialized transaction size, leaving room for the compute budget
+    let mut ixs = compute_budget_ixs(Some(compute_units), Some(1));
+    ixs.extend(items.iter().map(|(_, ix)| ix.clone()));
+    let tx = Transaction::new_unsigned(Message::new(&ixs, Some(payer)));
+    match bincode::serialized_size(&tx) {
+        Ok(size) => size as usize <= PACKET_DATA_SIZE,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::instruction::AccountMeta};
+
+    fn new_ix(num_accounts: usize) -> Instruction {
+        Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: (0..num_accounts)
+                .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+                .collect(),
+            data: vec![0; 8],
+        }
+    }
+
+    #[test]
+    fn test_pack_respects_batch_size() {
+        let payer = Pubkey::new_unique();
+        let items = (0..5).map(|i| (i, new_ix(1))).collect();
+        let batches = pack(&payer, items, 2, |_| DEFAULT_TASK_COMPUTE_UNITS);
+        assert_eq!(
+            batches.iter().map(|b| b.len()).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(batches[2][0].0, 4);
+    }
+
+    #[test]
+    fn test_pack_respects_packet_size() {
+        let payer = Pubkey::new_unique();
+        let items = (0..3).map(|i| (i, new_ix(30))).collect();
+        let batches = pack(&payer, items, 10, |_| DEFAULT_TASK_COMPUTE_UNITS);
+        assert!(batches.iter().all(|b| b.len() == 1));
+    }
+
+    #[test]
+    fn test_pack_respects_compute_limit() {
+        let payer = Pubkey::new_unique();
+        let items = (0..4).map(|i| (i, new_ix(1))).collect();
+        let batches = pack(&payer, items, 10, |i| match i {
+            0 => MAX_TX_COMPUTE_UNITS / 2,
+            _ => MAX_TX_COMPUTE_UNITS / 4,
+        });
+        assert_eq!(
+            batches.iter().map(|b| b.len()).collect::<Vec<_>>(),
+            vec![3, 1]
+        );
+    }
+
+    #[test]
+    fn test_pack_keeps_oversized_instruction() {
+        let payer = Pubkey::new_unique();
+        let batches = pack(&payer, vec![(0, new_ix(80))], 4, |_| {
+            DEFAULT_TASK_COMPUTE_UNITS
+        });
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 1);
+    }
+}
@@ -0,0 +1,173 @@
+use {
+    crate::batch::{DEFAULT_TASK_COMPUTE_UNITS, MAX_TX_COMPUTE_UNITS},
+    serde::Deserialize,
+    solana_sdk::{compute_budget::ComputeBudgetInstruction, instruction::Instruction},
+};
+
+/// How to size the compute unit limit of a task_exec transaction.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum ComputeUnitLimit {
+    /// Request a fixed number of compute units for every task.
+    Fixed { units: u32 },
+    /// Simulate the transaction and request the consumed units plus a safety margin.
+    Simulated { margin_percent: u32 },
+}
+
+/// How to price the compute units of a task_exec transaction.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum PriorityFee {
+    /// Pay a fixed price per compute unit.
+    Fixed { micro_lamports: u64 },
+    /// Pay a percentile of the prioritization fees recently paid for the same accounts.
+    ///
+    /// RPC nodes running Solana 1.10 don't serve recent prioritization fees, so the fixed
+    /// `fallback_micro_lamports` is paid when the RPC node doesn't support them.
+    Percentile {
+        percentile: u8,
+        max_micro_lamports: Option<u64>,
+        #[serde(default)]
+        fallback_micro_lamports: u64,
+    },
+}
+
+impl ComputeUnitLimit {
+    /// Compute units to reserve for a single task before anything is simulated.
+    pub fn task_units(&self) -> u64 {
+        match self {
+            ComputeUnitLimit::Fixed { units } => *units as u64,
+            ComputeUnitLimit::Simulated { .. } => DEFAULT_TASK_COMPUTE_UNITS,
+        }
+    }
+
+    /// Apply the configured safety margin to a simulated unit count.
+    pub fn with_margin(&self, units_consumed: u64) -> u64 {
+        match self {
+            ComputeUnitLimit::Fixed { .. } => units_consumed,
+            ComputeUnitLimit::Simulated { margin_percent } => units_consumed
+                .saturating_mul(100 + *margin_percent as u64)
+                .checked_div(100)
+                .unwrap_or(units_consumed),
+        }
+    }
+}
+
+/// Pick the given percentile out of a set of recently paid prioritization fees.
+pub fn fee_percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let percentile = percentile.min(100) as usize;
+    let idx = (fees.len() - 1) * percentile / 100;
+    fees[idx]
+}
+
+/// The additional fee, in lamports, for a number of compute units at a price in micro-lamports.
+pub fn additional_fee(units: u64, micro_lamports: u64) -> u32 {
+    (units as u128)
+        .saturating_mul(micro_lamports as u128)
+        .checked_div(1_000_000)
+        .unwrap_or(0)
+        .min(u32::MAX as u128) as u32
+}
+
+/// Build the ComputeBudget instructions to prepend to a transaction.
+///
+/// The cluster's compute budget program takes the unit limit and the priority fee in a single
+/// instruction, so pricing units without a limit requests the default units of a task.
+pub fn compute_budget_ixs(units: Option<u64>, micro_lamports: Option<u64>) -> Vec<Instruction> {
+    let micro_lamports = micro_lamports.unwrap_or(0);
+    if units.is_none() && micro_lamports == 0 {
+        return vec![];
+    }
+    let units = units
+        .unwrap_or(DEFAULT_TASK_COMPUTE_UNITS)
+        .min(MAX_TX_COMPUTE_UNITS);
+    vec![ComputeBudgetInstruction::request_units(
+        units as u32,
+        additional_fee(units, micro_lamports),
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::compute_budget};
+
+    #[test]
+    fn test_fee_percentile() {
+        let fees = vec![50, 10, 40, 20, 30];
+        assert_eq!(fee_percentile(fees.clone(), 0), 10);
+        assert_eq!(fee_percentile(fees.clone(), 50), 30);
+        assert_eq!(fee_percentile(fees.clone(), 100), 50);
+        assert_eq!(fee_percentile(fees, 255), 50);
+        assert_eq!(fee_percentile(vec![], 75), 0);
+    }
+
+    #[test]
+    fn test_additional_fee() {
+        assert_eq!(additional_fee(200_000, 0), 0);
+        assert_eq!(additional_fee(200_000, 5), 1);
+        assert_eq!(additional_fee(1_400_000, 1_000), 1_400);
+        assert_eq!(additional_fee(u64::MAX, u64::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn test_with_margin() {
+        let limit = ComputeUnitLimit::Simulated { margin_percent: 20 };
+        assert_eq!(limit.with_margin(100_000), 120_000);
+        let limit = ComputeUnitLimit::Fixed { units: 300_000 };
+        assert_eq!(limit.task_units(), 300_000);
+    }
+
+    #[test]
+    fn test_compute_budget_ixs() {
+        assert!(compute_budget_ixs(None, None).is_empty());
+        assert!(compute_budget_ixs(None, Some(0)).is_empty());
+
+        let ixs = compute_budget_ixs(Some(u64::MAX), Some(5));
+        assert_eq!(ixs.len(), 1);
+        assert_eq!(ixs[0].program_id, compute_budget::id());
+        assert_eq!(
+            ixs[0],
+            ComputeBudgetInstruction::request_units(MAX_TX_COMPUTE_UNITS as u32, 7)
+        );
+
+        let ixs = compute_budget_ixs(None, Some(1_000));
+        assert_eq!(
+            ixs,
+            vec![ComputeBudgetInstruction::request_units(
+                DEFAULT_TASK_COMPUTE_UNITS as u32,
+                200
+            )]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_strategies() {
+        let limit: ComputeUnitLimit =
+            serde_json::from_str(r#"{"strategy": "simulated", "margin_percent": 10}"#).unwrap();
+        assert_eq!(limit, ComputeUnitLimit::Simulated { margin_percent: 10 });
+
+        let fee: PriorityFee =
+            serde_json::from_str(r#"{"strategy": "fixed", "micro_lamports": 1000}"#).unwrap();
+        assert_eq!(
+            fee,
+            PriorityFee::Fixed {
+                micro_lamports: 1000
+            }
+        );
+
+        let fee: PriorityFee =
+            serde_json::from_str(r#"{"strategy": "percentile", "percentile": 75}"#).unwrap();
+        assert_eq!(
+            fee,
+            PriorityFee::Percentile {
+                percentile: 75,
+                max_micro_lamports: None,
+                fallback_micro_lamports: 0
+            }
+        );
+    }
+}
@@ -1,13 +1,34 @@
 use {
     cronos_sdk::scheduler::state::Task,
+    serde::{Deserialize, Serialize},
     solana_sdk::pubkey::Pubkey,
-    std::collections::{HashMap, HashSet},
+    std::{
+        collections::{HashMap, HashSet},
+        fs, io,
+        path::Path,
+    },
 };
 
+/// Seconds to hold off a task after its first failed simulation, doubled on each repeat.
+pub const BACKOFF_BASE: i64 = 10;
+
+/// Longest a task is ever held off for.
+pub const BACKOFF_MAX: i64 = 60 * 15;
+
 #[derive(Default)]
 pub struct TaskCache {
     pub data: HashMap<Pubkey, Task>,
     pub index: HashMap<i64, HashSet<Pubkey>>,
+    pub backoff: HashMap<Pubkey, Backoff>,
+    pub compute_units: HashMap<Pubkey, u64>,
+    pub daemon_balances: HashMap<Pubkey, u64>,
+    pub executed: HashMap<Pubkey, i64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Backoff {
+    pub failures: u32,
+    pub until: i64,
 }
 
 impl TaskCache {
@@ -15,6 +36,10 @@ impl TaskCache {
         TaskCache {
             data: HashMap::new(),
             index: HashMap::new(),
+            backoff: HashMap::new(),
+            compute_units: HashMap::new(),
+            daemon_balances: HashMap::new(),
+            executed: HashMap::new(),
         }
     }
 
@@ -49,4 +74,149 @@ impl TaskCache {
                 })
         });
     }
+
+    pub fn record_compute_units(&mut self, key: Pubkey, units: u64) {
+        self.compute_units.insert(key, units);
+    }
+
+    pub fn back_off(&mut self, key: Pubkey, now: i64) {
+        let failures = self.backoff.get(&key).map_or(0, |b| b.failures) + 1;
+        let delay = BACKOFF_BASE
+            .saturating_mul(1 << (failures - 1).min(16))
+            .min(BACKOFF_MAX);
+        self.backoff.insert(
+            key,
+            Backoff {
+                failures,
+                until: now + delay,
+            },
+        );
+    }
+
+    pub fn is_backed_off(&self, key: &Pubkey, now: i64) -> bool {
+        matches!(self.backoff.get(key), Some(b) if now < b.until)
+    }
+
+    pub fn clear_backoff(&mut self, key: &Pubkey) {
+        self.backoff.remove(key);
+    }
+
+    /// Record that a task was seen executing at the given cluster time.
+    pub fn mark_executed(&mut self, key: Pubkey, now: i64) {
+        self.executed.insert(key, now);
+    }
+
+    /// Whether the cached occurrence of a task has already been executed,
+    /// i.e. its account update with the next exec_at has not arrived yet.
+    pub fn is_executed(&self, key: &Pubkey, exec_at: i64) -> bool {
+        matches!(self.executed.get(key), Some(executed) if exec_at <= *executed)
+    }
+
+    /// The cache state worth keeping across restarts.
+    pub fn persisted_state(&self) -> PersistedState {
+        PersistedState {
+            backoff: self.backoff.clone(),
+            executed: self.executed.clone(),
+        }
+    }
+
+    /// Restore state saved by a previous run. Newer local state wins.
+    pub fn restore(&mut self, state: PersistedState) {
+        for (key, backoff) in state.backoff {
+            self.backoff.entry(key).or_insert(backoff);
+        }
+        for (key, executed) in state.executed {
+            let entry = self.executed.entry(key).or_insert(executed);
+            *entry = (*entry).max(executed);
+        }
+    }
+}
+
+/// Task backoffs and executions, flushed to disk on unload so a restarted plugin
+/// doesn't resubmit tasks it just executed or retry tasks it was holding off.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersistedState {
+    pub backoff: HashMap<Pubkey, Backoff>,
+    pub executed: HashMap<Pubkey, i64>,
+}
+
+impl PersistedState {
+    pub fn read_from<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let data = fs::read(path)?;
+        bincode::deserialize(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write the state through a temporary file so a crash never leaves a partial file behind.
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let data =
+            bincode::serialize(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let tmp_path = path.as_ref().with_extension("tmp");
+        fs::write(&tmp_path, data)?;
+        fs::rename(tmp_path, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_back_off() {
+        let mut cache = TaskCache::new();
+        let key = Pubkey::new_unique();
+        assert!(!cache.is_backed_off(&key, 100));
+
+        cache.back_off(key, 100);
+        assert!(cache.is_backed_off(&key, 109));
+        assert!(!cache.is_backed_off(&key, 110));
+
+        cache.back_off(key, 110);
+        assert_eq!(cache.backoff[&key].until, 130);
+
+        for _ in 0..40 {
+            cache.back_off(key, 200);
+        }
+        assert_eq!(cache.backoff[&key].until, 200 + BACKOFF_MAX);
+
+        cache.clear_backoff(&key);
+        assert!(!cache.is_backed_off(&key, 200));
+    }
+
+    #[test]
+    fn test_mark_executed() {
+        let mut cache = TaskCache::new();
+        let key = Pubkey::new_unique();
+        assert!(!cache.is_executed(&key, 100));
+
+        cache.mark_executed(key, 102);
+        assert!(cache.is_executed(&key, 100));
+        assert!(cache.is_executed(&key, 102));
+
+        // The next occurrence is due again
+        assert!(!cache.is_executed(&key, 160));
+    }
+
+    #[test]
+    fn test_persisted_state() {
+        let backed_off = Pubkey::new_unique();
+        let executed = Pubkey::new_unique();
+        let mut cache = TaskCache::new();
+        cache.back_off(backed_off, 100);
+        cache.mark_executed(executed, 102);
+
+        let path = std::env::temp_dir().join(format!("cronos-state-{}", std::process::id()));
+        cache.persisted_state().write_to(&path).unwrap();
+        let state = PersistedState::read_from(&path).unwrap();
+        assert_eq!(state, cache.persisted_state());
+        fs::remove_file(&path).unwrap();
+
+        // Executions observed since the restart are kept
+        let mut restarted = TaskCache::new();
+        restarted.mark_executed(executed, 160);
+        restarted.restore(state);
+        assert!(restarted.is_backed_off(&backed_off, 105));
+        assert_eq!(restarted.executed[&executed], 160);
+
+        assert!(PersistedState::read_from(&path).is_err());
+    }
 }
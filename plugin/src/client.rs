@@ -1,34 +1,272 @@
 use {
-    log::info,
-    solana_client_helpers::{Client, ClientResult, RpcClient},
+    crate::payers::FeePayers,
+    log::{info, warn},
+    serde::Deserialize,
+    serde_json::json,
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+        nonblocking::rpc_client::RpcClient,
+        rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
+        rpc_request::{RpcError, RpcRequest},
+        rpc_response::RpcSimulateTransactionResult,
+    },
     solana_sdk::{
-        commitment_config::CommitmentConfig, instruction::Instruction, signature::read_keypair,
-        signature::Signature, transaction::Transaction,
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair, Keypair, Signature, Signer},
+        transaction::Transaction,
+    },
+    std::{
+        fs::File,
+        sync::atomic::{AtomicBool, Ordering},
+        time::{Duration, Instant},
     },
-    std::fs::File,
 };
 
-pub trait RPCClient {
-    fn new(keypath: String, url: String) -> Client;
-    fn sign_and_submit(&self, ixs: &[Instruction], memo: &str) -> ClientResult<Signature>;
+/// How often to poll the status of a sent transaction.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long to wait for a sent transaction to reach the client's commitment.
+/// Roughly the lifetime of a blockhash, after which the transaction can no longer land.
+pub const CONFIRM_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// JSON-RPC error code returned for methods the RPC node doesn't serve.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Outcome of waiting on a sent transaction.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Confirmation {
+    Confirmed,
+    Cancelled,
+}
+
+/// Nonblocking RPC client which signs transactions with the node's keypairs.
+pub struct AsyncClient {
+    pub client: RpcClient,
+    /// The node's identity, which signs task executions.
+    pub identity: Keypair,
+    pub fee_payers: FeePayers,
+    /// Cleared once the RPC node reports it doesn't serve recent prioritization fees.
+    pub prioritization_fees_supported: AtomicBool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcPrioritizationFee {
+    prioritization_fee: u64,
 }
 
-// "http://127.0.0.1:8899"
+impl AsyncClient {
+    /// Create a client for the identity at `keypath`. The identity and the keypairs at
+    /// `fee_payer_keypaths` take turns paying fees, unless their balance is below `min_balance`.
+    pub fn new(
+        keypath: &str,
+        fee_payer_keypaths: &[String],
+        min_balance: u64,
+        url: String,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Self> {
+        let identity = read_keypair_file(keypath)?;
+        let mut fee_payers = vec![Keypair::from_bytes(&identity.to_bytes())
+            .map_err(|e| ClientErrorKind::Custom(e.to_string()))?];
+        for keypath in fee_payer_keypaths {
+            fee_payers.push(read_keypair_file(keypath)?);
+        }
+        Ok(Self {
+            client: RpcClient::new_with_commitment(url, commitment),
+            identity,
+            fee_payers: FeePayers::new(fee_payers, min_balance),
+            prioritization_fees_supported: AtomicBool::new(true),
+        })
+    }
+
+    pub fn identity_pubkey(&self) -> Pubkey {
+        self.identity.pubkey()
+    }
+
+    /// Sign a transaction with the next fee payer, and with the identity if it is a signer.
+    async fn sign(&self, ixs: &[Instruction]) -> ClientResult<Transaction> {
+        let fee_payer = self.fee_payers.next().ok_or_else(|| {
+            ClientErrorKind::Custom("Every fee payer's balance is below the minimum".to_string())
+        })?;
+        let mut tx = Transaction::new_with_payer(ixs, Some(&fee_payer.pubkey()));
+        let signer_keys = tx.message.signer_keys();
+        let mut signers = vec![fee_payer];
+        if fee_payer.pubkey() != self.identity_pubkey()
+            && signer_keys.contains(&&self.identity_pubkey())
+        {
+            signers.push(&self.identity);
+        }
+        tx.try_sign(&signers, self.client.get_latest_blockhash().await?)?;
+        Ok(tx)
+    }
 
-impl RPCClient for Client {
-    fn new(keypath: String, url: String) -> Client {
-        let payer = read_keypair(&mut File::open(keypath).unwrap()).unwrap();
-        let client = RpcClient::new_with_commitment::<String>(url, CommitmentConfig::confirmed());
-        Client { client, payer }
+    /// Fetch and record the balance of every fee payer.
+    pub async fn check_fee_payer_balances(&self) -> ClientResult<Vec<(Pubkey, u64)>> {
+        let pubkeys = self.fee_payers.pubkeys();
+        let accounts = self.client.get_multiple_accounts(&pubkeys).await?;
+        let mut balances = vec![];
+        for (i, (pubkey, account)) in pubkeys.into_iter().zip(accounts).enumerate() {
+            let lamports = account.map_or(0, |a| a.lamports);
+            self.fee_payers.update_balance(i, lamports);
+            balances.push((pubkey, lamports));
+        }
+        Ok(balances)
     }
 
-    fn sign_and_submit(&self, ixs: &[Instruction], memo: &str) -> ClientResult<Signature> {
+    /// Sign and send a transaction without waiting for it to be confirmed.
+    ///
+    /// Preflight is skipped since the plugin simulates task instructions before sending them.
+    pub async fn sign_and_send(&self, ixs: &[Instruction], memo: &str) -> ClientResult<Signature> {
         info!("{}", memo);
-        let payer = self.payer_pubkey();
-        let mut tx = Transaction::new_with_payer(ixs, Some(&payer));
-        tx.sign(&vec![&self.payer], self.latest_blockhash()?);
-        let sig = self.send_and_confirm_transaction(&tx)?;
-        info!("✅ {:?}", sig);
-        Ok(sig)
+        let tx = self.sign(ixs).await?;
+        self.client
+            .send_transaction_with_config(
+                &tx,
+                RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    ..RpcSendTransactionConfig::default()
+                },
+            )
+            .await
+    }
+
+    /// Poll the status of a sent transaction until it reaches the client's commitment.
+    ///
+    /// Returns an error if the transaction failed or timed out, and stops early
+    /// once `is_cancelled` returns true.
+    pub async fn confirm(
+        &self,
+        signature: &Signature,
+        timeout: Duration,
+        is_cancelled: impl Fn() -> bool,
+    ) -> ClientResult<Confirmation> {
+        let commitment = self.client.commitment();
+        let start = Instant::now();
+        loop {
+            let statuses = self
+                .client
+                .get_signature_statuses(&[*signature])
+                .await?
+                .value;
+            if let Some(Some(status)) = statuses.first() {
+                if let Some(err) = &status.err {
+                    return Err(err.clone().into());
+                }
+                if status.satisfies_commitment(commitment) {
+                    info!("✅ {:?}", signature);
+                    return Ok(Confirmation::Confirmed);
+                }
+            }
+            if is_cancelled() {
+                return Ok(Confirmation::Cancelled);
+            }
+            if start.elapsed() > timeout {
+                return Err(ClientErrorKind::Custom(format!(
+                    "Transaction {} was not confirmed within {:?}",
+                    signature, timeout
+                ))
+                .into());
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
     }
+
+    /// Sign and send a transaction, then wait for it to be confirmed.
+    pub async fn sign_and_submit(
+        &self,
+        ixs: &[Instruction],
+        memo: &str,
+    ) -> ClientResult<Signature> {
+        let signature = self.sign_and_send(ixs, memo).await?;
+        self.confirm(&signature, CONFIRM_TIMEOUT, || false).await?;
+        Ok(signature)
+    }
+
+    /// Simulate a transaction paid for by the identity.
+    ///
+    /// The transaction is left unsigned, so simulating doesn't take a fee payer's turn
+    /// or fetch a blockhash.
+    pub async fn simulate(
+        &self,
+        ixs: &[Instruction],
+    ) -> ClientResult<RpcSimulateTransactionResult> {
+        let tx = Transaction::new_with_payer(ixs, Some(&self.identity_pubkey()));
+        let res = self
+            .client
+            .simulate_transaction_with_config(
+                &tx,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            )
+            .await?;
+        Ok(res.value)
+    }
+
+    pub async fn get_account_data(&self, pubkey: &Pubkey) -> ClientResult<Vec<u8>> {
+        self.client.get_account_data(pubkey).await
+    }
+
+    /// Whether the account exists at the client's commitment.
+    pub async fn account_exists(&self, pubkey: &Pubkey) -> ClientResult<bool> {
+        let res = self
+            .client
+            .get_account_with_commitment(pubkey, self.client.commitment())
+            .await?;
+        Ok(res.value.is_some())
+    }
+
+    pub async fn get_slot(&self) -> ClientResult<u64> {
+        self.client.get_slot().await
+    }
+
+    /// Fetch the prioritization fees recently paid for transactions writing to the accounts.
+    ///
+    /// Returns None if the RPC node doesn't serve them, which is remembered so the node
+    /// isn't asked again.
+    pub async fn recent_prioritization_fees(
+        &self,
+        accounts: &[Pubkey],
+    ) -> ClientResult<Option<Vec<u64>>> {
+        if !self.prioritization_fees_supported.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let accounts: Vec<String> = accounts.iter().map(|a| a.to_string()).collect();
+        let res: ClientResult<Vec<RpcPrioritizationFee>> = self
+            .client
+            .send(
+                RpcRequest::Custom {
+                    method: "getRecentPrioritizationFees",
+                },
+                json!([accounts]),
+            )
+            .await;
+        match res {
+            Ok(fees) => Ok(Some(fees.iter().map(|f| f.prioritization_fee).collect())),
+            Err(err) if is_method_not_found(&err) => {
+                warn!("The RPC node doesn't serve recent prioritization fees, paying the fallback fee");
+                self.prioritization_fees_supported
+                    .store(false, Ordering::Relaxed);
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn is_method_not_found(err: &ClientError) -> bool {
+    matches!(
+        err.kind(),
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) if *code == METHOD_NOT_FOUND
+    )
+}
+
+fn read_keypair_file(keypath: &str) -> ClientResult<Keypair> {
+    read_keypair(&mut File::open(keypath)?).map_err(|e| {
+        ClientErrorKind::Custom(format!("Failed to read keypair {}: {}", keypath, e)).into()
+    })
 }
@@ -0,0 +1,163 @@
+use {
+    solana_program::clock::{Clock, DEFAULT_MS_PER_SLOT},
+    std::{
+        sync::RwLock,
+        time::{Duration, Instant},
+    },
+};
+
+/// How long slot updates may pause before the estimate falls back to the local clock.
+pub const FALLBACK_AFTER: Duration = Duration::from_secs(2);
+
+/// Estimates the cluster's unix timestamp between Clock sysvar updates.
+///
+/// The estimate is anchored to the latest Clock sysvar and advanced by confirmed slots.
+/// When slot updates stop arriving, it advances with the local clock instead.
+#[derive(Default)]
+pub struct ClusterClock {
+    state: RwLock<ClockState>,
+}
+
+#[derive(Default)]
+struct ClockState {
+    anchor: Option<Anchor>,
+    latest_slot: Option<(u64, Instant)>,
+    latest_tick: i64,
+}
+
+#[derive(Clone, Copy)]
+struct Anchor {
+    slot: u64,
+    unix_timestamp: i64,
+    observed_at: Instant,
+}
+
+impl ClusterClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Anchor the estimate to a Clock sysvar value.
+    pub fn observe_clock(&self, clock: &Clock, at: Instant) {
+        let mut w_state = self.state.write().unwrap();
+        let is_newer = match w_state.anchor {
+            Some(anchor) => clock.slot >= anchor.slot,
+            None => true,
+        };
+        if is_newer {
+            w_state.anchor = Some(Anchor {
+                slot: clock.slot,
+                unix_timestamp: clock.unix_timestamp,
+                observed_at: at,
+            });
+        }
+    }
+
+    /// Advance the estimate with a confirmed or rooted slot.
+    pub fn observe_slot(&self, slot: u64, at: Instant) {
+        let mut w_state = self.state.write().unwrap();
+        let is_newer = match w_state.latest_slot {
+            Some((latest_slot, _)) => slot > latest_slot,
+            None => true,
+        };
+        if is_newer {
+            w_state.latest_slot = Some((slot, at));
+        }
+    }
+
+    /// Time since the estimate was last anchored to a Clock sysvar value.
+    pub fn anchor_age(&self, at: Instant) -> Option<Duration> {
+        let r_state = self.state.read().unwrap();
+        r_state
+            .anchor
+            .map(|anchor| at.saturating_duration_since(anchor.observed_at))
+    }
+
+    /// Estimated cluster unix timestamp at the given instant.
+    pub fn estimate(&self, at: Instant) -> Option<i64> {
+        let r_state = self.state.read().unwrap();
+        let anchor = r_state.anchor?;
+
+        let elapsed = match r_state.latest_slot {
+            Some((slot, slot_at))
+                if slot >= anchor.slot
+                    && at.saturating_duration_since(slot_at) < FALLBACK_AFTER =>
+            {
+                Duration::from_millis((slot - anchor.slot) * DEFAULT_MS_PER_SLOT)
+                    + at.saturating_duration_since(slot_at)
+            }
+            _ => at.saturating_duration_since(anchor.observed_at),
+        };
+
+        Some(anchor.unix_timestamp + elapsed.as_secs() as i64)
+    }
+
+    /// Returns the estimated timestamp if it moved past the last one returned.
+    pub fn tick(&self, at: Instant) -> Option<i64> {
+        let now = self.estimate(at)?;
+        let mut w_state = self.state.write().unwrap();
+        if now > w_state.latest_tick {
+            w_state.latest_tick = now;
+            Some(now)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(slot: u64, unix_timestamp: i64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn test_estimate_from_slots() {
+        let start = Instant::now();
+        let cluster_clock = ClusterClock::new();
+        assert_eq!(cluster_clock.estimate(start), None);
+
+        cluster_clock.observe_clock(&clock(100, 1_000), start);
+        assert_eq!(cluster_clock.estimate(start), Some(1_000));
+
+        // 5 slots later is 2 seconds later
+        let at = start + Duration::from_millis(100);
+        cluster_clock.observe_slot(105, at);
+        assert_eq!(cluster_clock.estimate(at), Some(1_002));
+
+        // Rooted slots arriving late don't move the estimate back
+        cluster_clock.observe_slot(90, at);
+        assert_eq!(cluster_clock.estimate(at), Some(1_002));
+    }
+
+    #[test]
+    fn test_estimate_falls_back_to_local_clock() {
+        let start = Instant::now();
+        let cluster_clock = ClusterClock::new();
+        cluster_clock.observe_clock(&clock(100, 1_000), start);
+        cluster_clock.observe_slot(101, start);
+
+        let at = start + FALLBACK_AFTER + Duration::from_secs(1);
+        assert_eq!(cluster_clock.estimate(at), Some(1_003));
+    }
+
+    #[test]
+    fn test_tick() {
+        let start = Instant::now();
+        let cluster_clock = ClusterClock::new();
+        assert_eq!(cluster_clock.tick(start), None);
+
+        cluster_clock.observe_clock(&clock(100, 1_000), start);
+        assert_eq!(cluster_clock.tick(start), Some(1_000));
+        assert_eq!(cluster_clock.tick(start), None);
+
+        cluster_clock.observe_slot(103, start);
+        assert_eq!(cluster_clock.tick(start), Some(1_001));
+    }
+}
@@ -1,35 +1,491 @@
 use {
+    crate::budget::{ComputeUnitLimit, PriorityFee},
     serde::Deserialize,
+    solana_client::client_error::{ClientError, ClientErrorKind},
     solana_geyser_plugin_interface::geyser_plugin_interface::{
         GeyserPluginError, Result as PluginResult,
     },
-    std::{fs::File, path::Path},
+    solana_program::pubkey::Pubkey,
+    solana_sdk::commitment_config::{CommitmentConfig, CommitmentLevel},
+    std::{env, fs::File, net::SocketAddr, path::Path, str::FromStr, time::Duration},
 };
 
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+pub const DEFAULT_LOOKBACK_WINDOW: i64 = 60 * 15;
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 4;
+pub const DEFAULT_MIN_FEE_PAYER_BALANCE: u64 = 10_000_000;
+pub const DEFAULT_WORKER_COUNT: usize = 16;
+
 /// Plugin config.
+///
+/// Every field can be overridden with a `CRONOS_*` environment variable named after it,
+/// e.g. `CRONOS_RPC_URL`. List fields take comma-separated values.
 #[derive(Deserialize)]
 pub struct Config {
+    pub backup_grace_period: Option<i64>,
+    pub commitment: Option<CommitmentLevel>,
+    pub compute_unit_limit: Option<ComputeUnitLimit>,
+    #[serde(default)]
+    pub daemon_excludes: Vec<String>,
+    #[serde(default)]
+    pub daemon_includes: Vec<String>,
     pub keypath: String,
+    #[serde(default)]
+    pub keypaths: Vec<String>,
+    pub log_level: Option<String>,
+    pub lookback_window: Option<i64>,
+    pub max_batch_size: Option<usize>,
+    pub metrics_addr: Option<String>,
+    pub min_daemon_balance: Option<u64>,
+    pub min_fee_payer_balance: Option<u64>,
+    pub priority_fee: Option<PriorityFee>,
+    #[serde(default)]
+    pub program_excludes: Vec<String>,
     pub program_includes: Vec<String>,
+    pub retry: Option<RetryPolicy>,
     pub rpc_url: String,
+    pub state_path: Option<String>,
+    pub worker_count: Option<usize>,
+    pub ws_url: Option<String>,
+}
+
+/// How to retry task submissions that fail before reaching the cluster.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub delay_ms: u64,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            backup_grace_period: None,
+            commitment: None,
+            compute_unit_limit: None,
+            daemon_excludes: Vec::new(),
+            daemon_includes: Vec::new(),
             keypath: "".to_string(),
+            keypaths: Vec::new(),
+            log_level: None,
+            lookback_window: None,
+            max_batch_size: None,
+            metrics_addr: None,
+            min_daemon_balance: None,
+            min_fee_payer_balance: None,
+            priority_fee: None,
+            program_excludes: Vec::new(),
             program_includes: Vec::new(),
+            retry: None,
             rpc_url: "http://127.0.0.1:8899".to_string(),
+            state_path: None,
+            worker_count: None,
+            ws_url: None,
         }
     }
 }
 
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            delay_ms: 0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    /// Whether a submission that failed on the given attempt should be tried again.
+    /// Transactions rejected by the cluster are not retried.
+    pub fn should_retry(&self, attempt: u32, err: &ClientError) -> bool {
+        attempt < self.max_attempts && !matches!(err.kind(), ClientErrorKind::TransactionError(_))
+    }
+}
+
 impl Config {
-    /// Read plugin from JSON file.
+    /// Commitment level used for RPC requests.
+    pub fn commitment(&self) -> CommitmentConfig {
+        CommitmentConfig {
+            commitment: self.commitment.unwrap_or(CommitmentLevel::Confirmed),
+        }
+    }
+
+    /// Log filter passed to the logger, e.g. "info" or "cronos_plugin=debug".
+    pub fn log_level(&self) -> &str {
+        self.log_level.as_deref().unwrap_or(DEFAULT_LOG_LEVEL)
+    }
+
+    /// Number of seconds to look back for tasks that are due.
+    pub fn lookback_window(&self) -> i64 {
+        self.lookback_window.unwrap_or(DEFAULT_LOOKBACK_WINDOW)
+    }
+
+    /// Maximum number of tasks to pack into one transaction.
+    pub fn max_batch_size(&self) -> usize {
+        self.max_batch_size.unwrap_or(DEFAULT_MAX_BATCH_SIZE).max(1)
+    }
+
+    /// Lamports a fee payer must hold to be used.
+    pub fn min_fee_payer_balance(&self) -> u64 {
+        self.min_fee_payer_balance
+            .unwrap_or(DEFAULT_MIN_FEE_PAYER_BALANCE)
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry.clone().unwrap_or_default()
+    }
+
+    /// Maximum number of threads simulating or submitting tasks at once.
+    pub fn worker_count(&self) -> usize {
+        self.worker_count.unwrap_or(DEFAULT_WORKER_COUNT).max(1)
+    }
+
+    /// Websocket url of the RPC node, used by the standalone node for subscriptions.
+    /// Defaults to the rpc_url with a ws scheme on the next port, like the Solana CLI.
+    pub fn ws_url(&self) -> String {
+        match &self.ws_url {
+            Some(url) => url.clone(),
+            None => websocket_url(&self.rpc_url),
+        }
+    }
+
+    /// Read plugin from JSON file, apply environment overrides and validate the result.
     pub fn read_from<P: AsRef<Path>>(config_path: P) -> PluginResult<Self> {
-        let file = File::open(config_path)?;
-        let this: Self = serde_json::from_reader(file)
-            .map_err(|e| GeyserPluginError::ConfigFileReadError { msg: e.to_string() })?;
+        let config_path = config_path.as_ref();
+        let read_error = |msg: String| GeyserPluginError::ConfigFileReadError {
+            msg: format!("{}: {}", config_path.display(), msg),
+        };
+
+        let file = File::open(config_path).map_err(|e| read_error(e.to_string()))?;
+        let mut this: Self =
+            serde_json::from_reader(file).map_err(|e| read_error(e.to_string()))?;
+        this.apply_overrides(|key| env::var(key).ok())
+            .map_err(read_error)?;
+        this.validate().map_err(read_error)?;
         Ok(this)
     }
+
+    /// Override fields with the `CRONOS_*` variables returned by `var`.
+    pub fn apply_overrides(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        if let Some(v) = var("CRONOS_BACKUP_GRACE_PERIOD") {
+            self.backup_grace_period = Some(parse("CRONOS_BACKUP_GRACE_PERIOD", &v)?);
+        }
+        if let Some(v) = var("CRONOS_COMMITMENT") {
+            self.commitment = Some(
+                CommitmentLevel::from_str(&v)
+                    .map_err(|e| format!("CRONOS_COMMITMENT={}: {}", v, e))?,
+            );
+        }
+        if let Some(v) = var("CRONOS_DAEMON_EXCLUDES") {
+            self.daemon_excludes = split_list(&v);
+        }
+        if let Some(v) = var("CRONOS_DAEMON_INCLUDES") {
+            self.daemon_includes = split_list(&v);
+        }
+        if let Some(v) = var("CRONOS_KEYPATH") {
+            self.keypath = v;
+        }
+        if let Some(v) = var("CRONOS_KEYPATHS") {
+            self.keypaths = split_list(&v);
+        }
+        if let Some(v) = var("CRONOS_LOG_LEVEL") {
+            self.log_level = Some(v);
+        }
+        if let Some(v) = var("CRONOS_LOOKBACK_WINDOW") {
+            self.lookback_window = Some(parse("CRONOS_LOOKBACK_WINDOW", &v)?);
+        }
+        if let Some(v) = var("CRONOS_MAX_BATCH_SIZE") {
+            self.max_batch_size = Some(parse("CRONOS_MAX_BATCH_SIZE", &v)?);
+        }
+        if let Some(v) = var("CRONOS_METRICS_ADDR") {
+            self.metrics_addr = Some(v);
+        }
+        if let Some(v) = var("CRONOS_MIN_DAEMON_BALANCE") {
+            self.min_daemon_balance = Some(parse("CRONOS_MIN_DAEMON_BALANCE", &v)?);
+        }
+        if let Some(v) = var("CRONOS_MIN_FEE_PAYER_BALANCE") {
+            self.min_fee_payer_balance = Some(parse("CRONOS_MIN_FEE_PAYER_BALANCE", &v)?);
+        }
+        if let Some(v) = var("CRONOS_PROGRAM_EXCLUDES") {
+            self.program_excludes = split_list(&v);
+        }
+        if let Some(v) = var("CRONOS_PROGRAM_INCLUDES") {
+            self.program_includes = split_list(&v);
+        }
+        if let Some(v) = var("CRONOS_RETRY_MAX_ATTEMPTS") {
+            let mut retry = self.retry_policy();
+            retry.max_attempts = parse("CRONOS_RETRY_MAX_ATTEMPTS", &v)?;
+            self.retry = Some(retry);
+        }
+        if let Some(v) = var("CRONOS_RETRY_DELAY_MS") {
+            let mut retry = self.retry_policy();
+            retry.delay_ms = parse("CRONOS_RETRY_DELAY_MS", &v)?;
+            self.retry = Some(retry);
+        }
+        if let Some(v) = var("CRONOS_RPC_URL") {
+            self.rpc_url = v;
+        }
+        if let Some(v) = var("CRONOS_STATE_PATH") {
+            self.state_path = Some(v);
+        }
+        if let Some(v) = var("CRONOS_WORKER_COUNT") {
+            self.worker_count = Some(parse("CRONOS_WORKER_COUNT", &v)?);
+        }
+        if let Some(v) = var("CRONOS_WS_URL") {
+            self.ws_url = Some(v);
+        }
+        Ok(())
+    }
+
+    /// Check the config for values the plugin can't run with.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.keypath.is_empty() {
+            return Err("keypath is required".to_string());
+        }
+        if self
+            .keypaths
+            .iter()
+            .any(|k| k.is_empty() || *k == self.keypath)
+        {
+            return Err("keypaths must be distinct from keypath and not empty".to_string());
+        }
+        if !self.rpc_url.starts_with("http://") && !self.rpc_url.starts_with("https://") {
+            return Err(format!("rpc_url {} is not an http(s) url", self.rpc_url));
+        }
+        for (name, keys) in [
+            ("daemon_excludes", &self.daemon_excludes),
+            ("daemon_includes", &self.daemon_includes),
+            ("program_excludes", &self.program_excludes),
+            ("program_includes", &self.program_includes),
+        ] {
+            for key in keys {
+                Pubkey::from_str(key)
+                    .map_err(|_| format!("{} entry {} is not a pubkey", name, key))?;
+            }
+        }
+        if !self.ws_url().starts_with("ws://") && !self.ws_url().starts_with("wss://") {
+            return Err(format!("ws_url {} is not a ws(s) url", self.ws_url()));
+        }
+        if let Some(addr) = &self.metrics_addr {
+            SocketAddr::from_str(addr)
+                .map_err(|_| format!("metrics_addr {} is not a socket address", addr))?;
+        }
+        if matches!(self.backup_grace_period, Some(p) if p < 0) {
+            return Err("backup_grace_period cannot be negative".to_string());
+        }
+        if self.lookback_window() <= 0 {
+            return Err("lookback_window must be positive".to_string());
+        }
+        if self.max_batch_size == Some(0) {
+            return Err("max_batch_size must be positive".to_string());
+        }
+        if self.worker_count == Some(0) {
+            return Err("worker_count must be positive".to_string());
+        }
+        if self.retry_policy().max_attempts == 0 {
+            return Err("retry.max_attempts must be positive".to_string());
+        }
+        if matches!(self.priority_fee, Some(PriorityFee::Percentile { percentile, .. }) if percentile > 100)
+        {
+            return Err("priority_fee.percentile cannot be above 100".to_string());
+        }
+        if self.log_level().is_empty() {
+            return Err("log_level cannot be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn websocket_url(rpc_url: &str) -> String {
+    let (scheme, rest) = match rpc_url.split_once("://") {
+        Some(("https", rest)) => ("wss", rest),
+        Some((_, rest)) => ("ws", rest),
+        None => ("ws", rpc_url),
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    let authority = match authority.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) => format!("{}:{}", host, port.saturating_add(1)),
+            Err(_) => authority.to_string(),
+        },
+        None => authority.to_string(),
+    };
+    format!("{}://{}{}", scheme, authority, path)
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{}={} is not a valid value", key, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::HashMap};
+
+    fn base_config() -> Config {
+        Config {
+            keypath: "/tmp/id.json".to_string(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config: Config = serde_json::from_str(
+            r#"{"keypath": "id.json", "program_includes": [], "rpc_url": "http://127.0.0.1:8899"}"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.commitment(), CommitmentConfig::confirmed());
+        assert_eq!(config.log_level(), DEFAULT_LOG_LEVEL);
+        assert_eq!(config.lookback_window(), DEFAULT_LOOKBACK_WINDOW);
+        assert_eq!(config.worker_count(), DEFAULT_WORKER_COUNT);
+        assert_eq!(
+            config.min_fee_payer_balance(),
+            DEFAULT_MIN_FEE_PAYER_BALANCE
+        );
+        assert_eq!(config.retry_policy(), RetryPolicy::default());
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars: HashMap<&str, &str> = HashMap::from([
+            ("CRONOS_COMMITMENT", "finalized"),
+            ("CRONOS_KEYPATHS", "/tmp/a.json,/tmp/b.json"),
+            ("CRONOS_LOOKBACK_WINDOW", "60"),
+            (
+                "CRONOS_PROGRAM_INCLUDES",
+                "CronpZj5NbHj2Nb6WwEtf6A9anty9JfEQ1RnGoshQBaW, ",
+            ),
+            ("CRONOS_RETRY_MAX_ATTEMPTS", "3"),
+            ("CRONOS_RPC_URL", "https://api.devnet.solana.com"),
+            ("CRONOS_STATE_PATH", "/var/lib/cronos/state.bin"),
+            ("CRONOS_WORKER_COUNT", "2"),
+        ]);
+        let mut config = base_config();
+        config
+            .apply_overrides(|key| vars.get(key).map(|v| v.to_string()))
+            .unwrap();
+
+        assert_eq!(config.commitment(), CommitmentConfig::finalized());
+        assert_eq!(
+            config.keypaths,
+            vec!["/tmp/a.json".to_string(), "/tmp/b.json".to_string()]
+        );
+        assert_eq!(config.lookback_window(), 60);
+        assert_eq!(
+            config.program_includes,
+            vec!["CronpZj5NbHj2Nb6WwEtf6A9anty9JfEQ1RnGoshQBaW".to_string()]
+        );
+        assert_eq!(config.retry_policy().max_attempts, 3);
+        assert_eq!(config.rpc_url, "https://api.devnet.solana.com");
+        assert_eq!(
+            config.state_path,
+            Some("/var/lib/cronos/state.bin".to_string())
+        );
+        assert_eq!(config.worker_count(), 2);
+        assert!(config.validate().is_ok());
+
+        let mut config = base_config();
+        assert!(config
+            .apply_overrides(|key| (key == "CRONOS_WORKER_COUNT").then(|| "many".to_string()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_ws_url() {
+        let config = Config {
+            rpc_url: "http://127.0.0.1:8899".to_string(),
+            ..base_config()
+        };
+        assert_eq!(config.ws_url(), "ws://127.0.0.1:8900");
+
+        let config = Config {
+            rpc_url: "https://api.devnet.solana.com/rpc".to_string(),
+            ..base_config()
+        };
+        assert_eq!(config.ws_url(), "wss://api.devnet.solana.com/rpc");
+
+        let config = Config {
+            ws_url: Some("wss://ws.example.com".to_string()),
+            ..base_config()
+        };
+        assert_eq!(config.ws_url(), "wss://ws.example.com");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(base_config().validate().is_ok());
+
+        let mut invalid = vec![];
+        invalid.push(Config {
+            keypath: "".to_string(),
+            ..base_config()
+        });
+        invalid.push(Config {
+            keypaths: vec!["/tmp/id.json".to_string()],
+            ..base_config()
+        });
+        invalid.push(Config {
+            rpc_url: "127.0.0.1:8899".to_string(),
+            ..base_config()
+        });
+        invalid.push(Config {
+            program_includes: vec!["not-a-pubkey".to_string()],
+            ..base_config()
+        });
+        invalid.push(Config {
+            daemon_excludes: vec!["not-a-pubkey".to_string()],
+            ..base_config()
+        });
+        invalid.push(Config {
+            metrics_addr: Some("localhost".to_string()),
+            ..base_config()
+        });
+        invalid.push(Config {
+            ws_url: Some("http://127.0.0.1:8900".to_string()),
+            ..base_config()
+        });
+        invalid.push(Config {
+            lookback_window: Some(0),
+            ..base_config()
+        });
+        invalid.push(Config {
+            worker_count: Some(0),
+            ..base_config()
+        });
+        invalid.push(Config {
+            priority_fee: Some(PriorityFee::Percentile {
+                percentile: 101,
+                max_micro_lamports: None,
+                fallback_micro_lamports: 0,
+            }),
+            ..base_config()
+        });
+        invalid.push(Config {
+            retry: Some(RetryPolicy {
+                max_attempts: 0,
+                delay_ms: 0,
+            }),
+            ..base_config()
+        });
+        for config in invalid {
+            assert!(config.validate().is_err());
+        }
+    }
 }
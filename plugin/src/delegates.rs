@@ -0,0 +1,106 @@
+use {cronos_sdk::network::state::Pool, solana_program::pubkey::Pubkey, std::collections::HashSet};
+
+/// Replica of the network pool, which holds the nodes currently expected to execute tasks.
+pub struct Delegates {
+    pub address: Pubkey,
+    delegates: HashSet<Pubkey>,
+    /// The most recently drawn delegate, which cranks the network ahead of the others.
+    elected: Option<Pubkey>,
+}
+
+impl Delegates {
+    pub fn new() -> Self {
+        Self {
+            address: Pool::pda().0,
+            delegates: HashSet::new(),
+            elected: None,
+        }
+    }
+
+    pub fn update(&mut self, pool: &Pool) {
+        self.delegates = pool.delegates.iter().copied().collect();
+        self.elected = pool.delegates.back().copied();
+    }
+
+    /// Whether a node should crank the network right away. Other delegates only back it up.
+    /// Every node is elected while the pool is empty.
+    pub fn is_elected(&self, node: &Pubkey) -> bool {
+        self.elected.is_none() || self.elected == Some(*node)
+    }
+
+    pub fn is_delegate(&self, node: &Pubkey) -> bool {
+        self.delegates.is_empty() || self.delegates.contains(node)
+    }
+
+    /// Whether a node should execute a task that was due at `exec_at`.
+    ///
+    /// Delegates always execute. When a backup grace period is configured, other nodes
+    /// execute tasks that the delegates have left overdue for at least that long.
+    pub fn should_execute(
+        &self,
+        node: &Pubkey,
+        exec_at: i64,
+        now: i64,
+        backup_grace_period: Option<i64>,
+    ) -> bool {
+        if self.is_delegate(node) {
+            return true;
+        }
+        match backup_grace_period {
+            Some(grace_period) => now >= exec_at.saturating_add(grace_period),
+            None => false,
+        }
+    }
+}
+
+impl Default for Delegates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delegates(keys: &[Pubkey]) -> Delegates {
+        Delegates {
+            address: Pubkey::new_unique(),
+            delegates: keys.iter().copied().collect(),
+            elected: keys.last().copied(),
+        }
+    }
+
+    #[test]
+    fn test_empty_pool_executes() {
+        let delegates = delegates(&[]);
+        assert!(delegates.should_execute(&Pubkey::new_unique(), 100, 100, None));
+    }
+
+    #[test]
+    fn test_only_delegates_execute() {
+        let node = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let delegates = delegates(&[node]);
+        assert!(delegates.should_execute(&node, 100, 100, None));
+        assert!(!delegates.should_execute(&other, 100, 100, None));
+        assert!(!delegates.should_execute(&other, 100, 1_000, None));
+    }
+
+    #[test]
+    fn test_backup_after_grace_period() {
+        let other = Pubkey::new_unique();
+        let delegates = delegates(&[Pubkey::new_unique()]);
+        assert!(!delegates.should_execute(&other, 100, 129, Some(30)));
+        assert!(delegates.should_execute(&other, 100, 130, Some(30)));
+    }
+
+    #[test]
+    fn test_newest_delegate_is_elected() {
+        let (oldest, newest) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(delegates(&[]).is_elected(&oldest));
+        let delegates = delegates(&[oldest, newest]);
+        assert!(delegates.is_elected(&newest));
+        assert!(!delegates.is_elected(&oldest));
+    }
+}
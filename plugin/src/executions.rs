@@ -0,0 +1,182 @@
+use {
+    solana_program::{hash::hash, instruction::CompiledInstruction, pubkey::Pubkey},
+    solana_sdk::{message::VersionedMessage, transaction::SanitizedTransaction},
+    std::{
+        collections::HashMap,
+        time::{Duration, Instant},
+    },
+};
+
+/// Index of the task account in a task_exec instruction.
+const TASK_EXEC_TASK_INDEX: usize = 6;
+
+/// Anchor discriminator of the task_exec instruction.
+pub fn task_exec_discriminator() -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(b"global:task_exec").to_bytes()[..8]);
+    discriminator
+}
+
+/// Task accounts executed by the task_exec instructions of a transaction.
+pub fn executed_tasks(tx: &SanitizedTransaction, scheduler_program_id: &Pubkey) -> Vec<Pubkey> {
+    match tx.to_versioned_transaction().message {
+        VersionedMessage::Legacy(message) => task_keys(
+            &message.account_keys,
+            &message.instructions,
+            scheduler_program_id,
+        ),
+        VersionedMessage::V0(message) => task_keys(
+            &message.account_keys,
+            &message.instructions,
+            scheduler_program_id,
+        ),
+    }
+}
+
+fn task_keys(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    scheduler_program_id: &Pubkey,
+) -> Vec<Pubkey> {
+    let discriminator = task_exec_discriminator();
+    instructions
+        .iter()
+        .filter(|ix| account_keys.get(ix.program_id_index as usize) == Some(scheduler_program_id))
+        .filter(|ix| ix.data.starts_with(&discriminator))
+        .flat_map(|ix| ix.accounts.get(TASK_EXEC_TASK_INDEX))
+        .flat_map(|index| account_keys.get(*index as usize))
+        .copied()
+        .collect()
+}
+
+/// Task submissions this node has started and not yet finished.
+#[derive(Default)]
+pub struct InFlight {
+    submissions: HashMap<Pubkey, Submission>,
+}
+
+struct Submission {
+    started: Instant,
+    cancelled: bool,
+}
+
+impl InFlight {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a submission, returning false if one is already in flight.
+    pub fn start(&mut self, key: Pubkey) -> bool {
+        if self.submissions.contains_key(&key) {
+            return false;
+        }
+        self.submissions.insert(
+            key,
+            Submission {
+                started: Instant::now(),
+                cancelled: false,
+            },
+        );
+        true
+    }
+
+    /// Cancel a submission made redundant by another node's execution.
+    pub fn cancel(&mut self, key: &Pubkey) -> bool {
+        match self.submissions.get_mut(key) {
+            Some(submission) => {
+                submission.cancelled = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_cancelled(&self, key: &Pubkey) -> bool {
+        matches!(self.submissions.get(key), Some(s) if s.cancelled)
+    }
+
+    /// Time since the submission started, if it is in flight.
+    pub fn elapsed(&self, key: &Pubkey) -> Option<Duration> {
+        self.submissions.get(key).map(|s| s.started.elapsed())
+    }
+
+    pub fn finish(&mut self, key: &Pubkey) {
+        self.submissions.remove(key);
+    }
+
+    pub fn len(&self) -> usize {
+        self.submissions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.submissions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_program::instruction::{AccountMeta, Instruction},
+        solana_sdk::message::Message,
+    };
+
+    fn task_exec_ix(scheduler: Pubkey, bot: Pubkey, task: Pubkey) -> Instruction {
+        let mut accounts = vec![AccountMeta::new(bot, true)];
+        accounts.extend((0..5).map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false)));
+        accounts.push(AccountMeta::new(task, false));
+        Instruction {
+            program_id: scheduler,
+            accounts,
+            data: task_exec_discriminator().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_task_keys() {
+        let scheduler = Pubkey::new_unique();
+        let bot = Pubkey::new_unique();
+        let task_a = Pubkey::new_unique();
+        let task_b = Pubkey::new_unique();
+
+        // Other scheduler instructions and programs are ignored
+        let mut other_ix = task_exec_ix(scheduler, bot, Pubkey::new_unique());
+        other_ix.data = vec![0; 8];
+        let other_program_ix = task_exec_ix(Pubkey::new_unique(), bot, Pubkey::new_unique());
+
+        let message = Message::new(
+            &[
+                task_exec_ix(scheduler, bot, task_a),
+                other_ix,
+                other_program_ix,
+                task_exec_ix(scheduler, bot, task_b),
+            ],
+            Some(&bot),
+        );
+        assert_eq!(
+            task_keys(&message.account_keys, &message.instructions, &scheduler),
+            vec![task_a, task_b]
+        );
+    }
+
+    #[test]
+    fn test_in_flight() {
+        let key = Pubkey::new_unique();
+        let mut in_flight = InFlight::new();
+        assert!(!in_flight.cancel(&key));
+
+        assert!(in_flight.is_empty());
+        assert!(in_flight.start(key));
+        assert!(!in_flight.start(key));
+        assert_eq!(in_flight.len(), 1);
+        assert!(in_flight.elapsed(&key).is_some());
+        assert!(!in_flight.is_cancelled(&key));
+
+        assert!(in_flight.cancel(&key));
+        assert!(in_flight.is_cancelled(&key));
+
+        in_flight.finish(&key);
+        assert!(in_flight.elapsed(&key).is_none());
+        assert!(in_flight.start(key));
+    }
+}
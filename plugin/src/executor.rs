@@ -0,0 +1,1108 @@
+use {
+    crate::{
+        batch::{self, DEFAULT_TASK_COMPUTE_UNITS, MAX_TX_COMPUTE_UNITS},
+        budget::{self, ComputeUnitLimit, PriorityFee},
+        cache::PersistedState,
+        client::{Confirmation, CONFIRM_TIMEOUT, POLL_INTERVAL},
+        clock::ClusterClock,
+        executions::InFlight,
+        metrics,
+        resolver::resolve_accounts,
+        settings::{self, Settings},
+        simulation::{self, SimulationOutcome},
+        AsyncClient, Bucket, Config, Delegates, Metrics, TaskCache,
+    },
+    bincode::deserialize,
+    cronos_sdk::{
+        network::{
+            instruction::{node_heartbeat, pool_cycle},
+            snapshot::{find_snapshot_entry, snapshot_transactions},
+            state::{
+                find_slot_hash, is_commit_expired, next_nonce, Config as NetworkConfig, Node, Pool,
+                Registry, RegistryPage, Snapshot, SnapshotPage,
+            },
+        },
+        scheduler::state::{Daemon, Fee, Task},
+    },
+    log::{debug, info, warn},
+    solana_client::client_error::{ClientErrorKind, Result as ClientResult},
+    solana_program::{clock::Clock, pubkey::Pubkey, sysvar},
+    solana_sdk::instruction::Instruction,
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex, RwLock,
+        },
+        thread,
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+    tokio::runtime::Handle,
+    tokio_util::sync::CancellationToken,
+};
+
+/// How often the local timer checks whether cluster time moved to a new second.
+const TICK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How stale the Clock sysvar anchor may get before it is fetched over RPC.
+const REANCHOR_AFTER: Duration = Duration::from_secs(30);
+
+/// How long shutting down waits for in-flight submissions to confirm.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often shutting down checks whether in-flight submissions have finished.
+const DRAIN_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How often to check the fee payers' balances.
+const BALANCE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often to record the node's last-seen time on chain. Stay well inside the network's
+/// heartbeat window, or the node is skipped when the pool cycles.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How long to wait before retrying a network snapshot crank that failed.
+const CRANK_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long delegates other than the elected one wait for it to crank the network, before
+/// cranking in its place.
+const CRANK_BACKUP_DELAY: Duration = Duration::from_secs(30);
+
+/// How many times to poll for the hash of a pool draw's committed slot, which is only recorded
+/// once a later slot is processed.
+const SLOT_HASH_POLLS: usize = 10;
+
+/// Replicates tasks and executes them when they come due.
+///
+/// The executor is fed account, slot and execution updates by its host, either the
+/// geyser plugin or the standalone node, and runs all network requests on `runtime`.
+#[derive(Clone)]
+pub struct Executor {
+    bucket: Arc<Mutex<Bucket>>,
+    cache: Arc<RwLock<TaskCache>>,
+    clock: Arc<ClusterClock>,
+    crank_authority: Arc<RwLock<Option<Pubkey>>>,
+    cranking: Arc<AtomicBool>,
+    cycling: Arc<AtomicBool>,
+    delegates: Arc<RwLock<Delegates>>,
+    in_flight: Arc<Mutex<InFlight>>,
+    metrics: Arc<Metrics>,
+    network_config_address: Pubkey,
+    registry_address: Pubkey,
+    runtime: Handle,
+    settings: Arc<RwLock<Arc<Settings>>>,
+    shutdown: CancellationToken,
+    latest_clock_value: i64,
+}
+
+#[derive(Error, Debug)]
+pub enum ExecutorError {
+    #[error("Error deserializing task data")]
+    TaskAccountInfoError,
+
+    #[error("Error deserializing sysvar clock data")]
+    ClockAccountInfoError,
+
+    #[error("Error deserializing network pool data")]
+    PoolAccountInfoError,
+
+    #[error("Error deserializing network config data")]
+    NetworkConfigAccountInfoError,
+
+    #[error("Error deserializing network registry data")]
+    RegistryAccountInfoError,
+
+    #[error("Error starting the metrics server. Error message: ({msg})")]
+    MetricsServerError { msg: String },
+}
+
+impl Executor {
+    /// Start an executor with settings loaded from `config_path`, reloading them when the file changes.
+    pub fn new(
+        config_path: &str,
+        settings: Settings,
+        runtime: Handle,
+    ) -> Result<Self, ExecutorError> {
+        let settings = Arc::new(RwLock::new(Arc::new(settings)));
+        settings::watch(config_path, &settings);
+
+        // The metrics server is bound once and is not affected by config reloads
+        let metrics = Arc::new(Metrics::new());
+        if let Some(addr) = &settings.read().unwrap().config.metrics_addr {
+            metrics::serve(addr.as_str(), metrics.clone())
+                .map_err(|e| ExecutorError::MetricsServerError { msg: e.to_string() })?;
+        }
+
+        let executor = Self {
+            bucket: Arc::new(Mutex::new(Bucket::new())),
+            cache: Arc::new(RwLock::new(TaskCache::new())),
+            clock: Arc::new(ClusterClock::new()),
+            crank_authority: Arc::new(RwLock::new(None)),
+            cranking: Arc::new(AtomicBool::new(false)),
+            cycling: Arc::new(AtomicBool::new(false)),
+            delegates: Arc::new(RwLock::new(Delegates::new())),
+            in_flight: Arc::new(Mutex::new(InFlight::new())),
+            metrics,
+            network_config_address: NetworkConfig::pda().0,
+            registry_address: Registry::pda().0,
+            runtime,
+            settings,
+            shutdown: CancellationToken::new(),
+            latest_clock_value: 0,
+        };
+        executor.restore_state();
+        executor.load_network_config();
+        executor.load_pool();
+        executor.crank_snapshot();
+
+        // Track cluster time from the Clock sysvar and slot updates
+        executor.anchor_clock();
+        executor.start_timer();
+        executor.start_balance_monitor();
+        executor.start_heartbeat();
+        Ok(executor)
+    }
+
+    pub fn client(&self) -> Arc<AsyncClient> {
+        self.settings().client.clone()
+    }
+
+    pub fn config(&self) -> Arc<Config> {
+        self.settings().config.clone()
+    }
+
+    pub fn settings(&self) -> Arc<Settings> {
+        self.settings.read().unwrap().clone()
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.is_cancelled()
+    }
+
+    /// Replicate an account update.
+    ///
+    /// The network config, pool and registry, the Clock sysvar and the scheduler's tasks and
+    /// daemons are tracked, other accounts are ignored.
+    pub fn update_account(
+        &self,
+        key: Pubkey,
+        owner: &Pubkey,
+        lamports: u64,
+        data: &[u8],
+    ) -> Result<(), ExecutorError> {
+        // Replicate the network pool to know which nodes are delegates
+        if self.delegates.read().unwrap().address == key {
+            let pool = Pool::try_from(data.to_vec())
+                .map_err(|_err| ExecutorError::PoolAccountInfoError)?;
+            info!("💽 Replicating network pool {:?}", pool.delegates);
+            self.delegates.write().unwrap().update(&pool);
+            if pool.commit_slot.is_some() {
+                self.cycle_pool();
+            }
+            return Ok(());
+        }
+
+        // Track the network's crank authority, whose tasks every node executes
+        if key == self.network_config_address {
+            let config = NetworkConfig::try_from(data.to_vec())
+                .map_err(|_err| ExecutorError::NetworkConfigAccountInfoError)?;
+            *self.crank_authority.write().unwrap() = Some(config.crank_authority);
+            return Ok(());
+        }
+
+        // Crank the network snapshot while the registry is locked
+        if key == self.registry_address {
+            let registry = Registry::try_from(data.to_vec())
+                .map_err(|_err| ExecutorError::RegistryAccountInfoError)?;
+            if registry.is_locked {
+                self.crank_snapshot();
+            }
+            return Ok(());
+        }
+
+        if !self.settings().filter.wants_program(owner.as_ref()) {
+            return Ok(());
+        }
+
+        debug!("Updating account {:?} with owner {:?}", key, owner);
+
+        if key == sysvar::clock::id() {
+            let clock =
+                deserialize::<Clock>(data).map_err(|_err| ExecutorError::ClockAccountInfoError)?;
+            self.update_clock(&clock);
+        } else if *owner == cronos_sdk::SCHEDULER_PROGRAM_ID {
+            // Track daemon balances for the min balance filter
+            if Daemon::try_from(data.to_vec()).is_ok() {
+                let mut w_cache = self.cache.write().unwrap();
+                w_cache.daemon_balances.insert(key, lamports);
+                return Ok(());
+            }
+
+            let task = Task::try_from(data.to_vec())
+                .map_err(|_err| ExecutorError::TaskAccountInfoError)?;
+            self.replicate_task(key, task);
+        }
+        Ok(())
+    }
+
+    /// Anchor cluster time to a Clock sysvar value.
+    pub fn update_clock(&self, clock: &Clock) {
+        self.metrics
+            .latest_clock
+            .fetch_max(clock.unix_timestamp, Ordering::Relaxed);
+        self.clock.observe_clock(clock, Instant::now());
+        self.tick();
+    }
+
+    /// Advance cluster time with a new slot.
+    pub fn update_slot(&self, slot: u64) {
+        self.clock.observe_slot(slot, Instant::now());
+        self.tick();
+    }
+
+    /// Record tasks seen executing in a transaction paid for by `fee_payer`.
+    pub fn update_executions(&self, fee_payer: &Pubkey, tasks: &[Pubkey]) {
+        let is_own = self.client().fee_payers.contains(fee_payer);
+        let now = self.clock.estimate(Instant::now());
+        let mut w_cache = self.cache.write().unwrap();
+        let mut w_in_flight = self.in_flight.lock().unwrap();
+        for key in tasks {
+            debug!("Task {} executed by {}", key, fee_payer);
+            if let Some(now) = now {
+                w_cache.mark_executed(*key, now);
+            }
+            if is_own {
+                if let Some(latency) = w_in_flight.elapsed(key) {
+                    self.metrics.record_confirmation(latency);
+                }
+            } else if w_in_flight.cancel(key) {
+                info!("⏹ Task {} was executed by another node", key);
+                self.metrics.record_preempted();
+            }
+        }
+    }
+
+    /// Stop starting new work, wait for submissions that were already sent to confirm,
+    /// and flush the task state to disk.
+    pub fn shutdown(&self) {
+        self.shutdown.cancel();
+        self.drain();
+        self.flush_state();
+    }
+
+    /// Wait for in-flight submissions to finish, up to the drain timeout.
+    fn drain(&self) {
+        let start = Instant::now();
+        loop {
+            {
+                let r_in_flight = self.in_flight.lock().unwrap();
+                if r_in_flight.is_empty() {
+                    return;
+                }
+                if start.elapsed() > DRAIN_TIMEOUT {
+                    info!("Abandoning {} in-flight submissions", r_in_flight.len());
+                    return;
+                }
+            }
+            thread::sleep(DRAIN_INTERVAL);
+        }
+    }
+
+    /// Restore the task state flushed by the previous run, if there is one.
+    fn restore_state(&self) {
+        let path = match &self.config().state_path {
+            Some(path) => path.clone(),
+            None => return,
+        };
+        match PersistedState::read_from(&path) {
+            Ok(state) => {
+                info!("Restored task state from {}", path);
+                self.cache.write().unwrap().restore(state);
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => info!("Failed to restore task state from {}: {}", path, err),
+        }
+    }
+
+    /// Write the task state to disk so the next run can pick it up.
+    fn flush_state(&self) {
+        let path = match &self.config().state_path {
+            Some(path) => path.clone(),
+            None => return,
+        };
+        let state = self.cache.read().unwrap().persisted_state();
+        match state.write_to(&path) {
+            Ok(()) => info!("Flushed task state to {}", path),
+            Err(err) => info!("Failed to flush task state to {}: {}", path, err),
+        }
+    }
+
+    fn is_cancelled(&self, key: &Pubkey) -> bool {
+        self.in_flight.lock().unwrap().is_cancelled(key)
+    }
+
+    fn replicate_task(&self, key: Pubkey, task: Task) {
+        info!("💽 Replicating task {}", key);
+        let mut w_cache = self.cache.write().unwrap();
+        match task.exec_at {
+            Some(_t) => w_cache.insert(key, task),
+            None => w_cache.delete(key),
+        }
+        self.metrics
+            .cached_tasks
+            .store(w_cache.data.len() as u64, Ordering::Relaxed);
+    }
+
+    /// Load the network config over RPC, later updates are replicated from account notifications.
+    fn load_network_config(&self) {
+        let executor = self.clone();
+        self.runtime.spawn(async move {
+            let data = executor
+                .client()
+                .get_account_data(&executor.network_config_address)
+                .await;
+            match data {
+                Ok(data) => match NetworkConfig::try_from(data) {
+                    Ok(config) => {
+                        *executor.crank_authority.write().unwrap() = Some(config.crank_authority)
+                    }
+                    Err(err) => info!("Failed to deserialize the network config: {}", err),
+                },
+                Err(err) => info!("Failed to fetch the network config: {}", err),
+            }
+        });
+    }
+
+    /// Load the network pool over RPC, later updates are replicated from account notifications.
+    fn load_pool(&self) {
+        let executor = self.clone();
+        self.runtime.spawn(async move {
+            let address = executor.delegates.read().unwrap().address;
+            match executor.client().get_account_data(&address).await {
+                Ok(data) => match Pool::try_from(data) {
+                    Ok(pool) => {
+                        executor.delegates.write().unwrap().update(&pool);
+                        if pool.commit_slot.is_some() {
+                            executor.cycle_pool();
+                        }
+                    }
+                    Err(err) => info!("Failed to deserialize the network pool: {}", err),
+                },
+                Err(err) => info!("Failed to fetch the network pool: {}", err),
+            }
+        });
+    }
+
+    /// Capture and rotate in the next network snapshot if the registry is locked,
+    /// retrying until the registry is unlocked. Only pool delegates crank snapshots, and
+    /// only the elected delegate cranks right away.
+    fn crank_snapshot(&self) {
+        if self.cranking.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let executor = self.clone();
+        self.runtime.spawn(async move {
+            if !executor.is_elected() {
+                tokio::select! {
+                    _ = executor.shutdown.cancelled() => {}
+                    _ = tokio::time::sleep(CRANK_BACKUP_DELAY) => {}
+                }
+            }
+            while !executor.is_shutting_down() {
+                match executor.crank_snapshot_once().await {
+                    Ok(()) => break,
+                    Err(err) => info!("Failed to crank the network snapshot: {}", err),
+                }
+                tokio::select! {
+                    _ = executor.shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(CRANK_RETRY_INTERVAL) => {}
+                }
+            }
+            executor.cranking.store(false, Ordering::SeqCst);
+        });
+    }
+
+    async fn crank_snapshot_once(&self) -> ClientResult<()> {
+        let client = self.client();
+        let registry = Registry::try_from(client.get_account_data(&self.registry_address).await?)
+            .map_err(|err| ClientErrorKind::Custom(err.to_string()))?;
+        if !registry.is_locked || !self.is_delegate() {
+            return Ok(());
+        }
+
+        // Resume from the snapshot in progress, if another node already opened it
+        let mut registry_pages = vec![];
+        for id in 0..registry.page_count {
+            let data = client.get_account_data(&RegistryPage::pda(id).0).await?;
+            registry_pages.push(
+                RegistryPage::try_from(data)
+                    .map_err(|err| ClientErrorKind::Custom(err.to_string()))?,
+            );
+        }
+        let snapshot = client
+            .get_account_data(&Snapshot::pda(registry.snapshot_count).0)
+            .await
+            .ok()
+            .and_then(|data| Snapshot::try_from(data).ok());
+
+        let txs = snapshot_transactions(
+            client.identity_pubkey(),
+            &registry,
+            &registry_pages,
+            snapshot.as_ref(),
+        );
+        for ixs in txs {
+            if self.is_shutting_down() {
+                return Ok(());
+            }
+            client
+                .sign_and_submit(&ixs, "📸 Cranking network snapshot")
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Draw the pool's committed delegate from the current snapshot. Only pool delegates cycle
+    /// the pool, and only the elected delegate cycles it right away.
+    fn cycle_pool(&self) {
+        if self.cycling.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let executor = self.clone();
+        self.runtime.spawn(async move {
+            if !executor.is_elected() {
+                tokio::select! {
+                    _ = executor.shutdown.cancelled() => {}
+                    _ = tokio::time::sleep(CRANK_BACKUP_DELAY) => {}
+                }
+            }
+            if !executor.is_shutting_down() {
+                if let Err(err) = executor.cycle_pool_once().await {
+                    info!("Failed to cycle the network pool: {}", err);
+                }
+            }
+            executor.cycling.store(false, Ordering::SeqCst);
+        });
+    }
+
+    async fn cycle_pool_once(&self) -> ClientResult<()> {
+        if !self.is_delegate() {
+            return Ok(());
+        }
+
+        // The crank authority commits draws on a schedule. A draw which was already cycled, or
+        // whose slot hash has aged out, is left for the next commit.
+        let client = self.client();
+        let pool_pubkey = Pool::pda().0;
+        let pool = Pool::try_from(client.get_account_data(&pool_pubkey).await?)
+            .map_err(|err| ClientErrorKind::Custom(err.to_string()))?;
+        let commit_slot = match pool.commit_slot {
+            Some(commit_slot) if !is_commit_expired(commit_slot, client.get_slot().await?) => {
+                commit_slot
+            }
+            _ => return Ok(()),
+        };
+
+        let registry = Registry::try_from(client.get_account_data(&self.registry_address).await?)
+            .map_err(|err| ClientErrorKind::Custom(err.to_string()))?;
+        let snapshot_id = registry.snapshot_count.saturating_sub(1);
+        let snapshot_pubkey = Snapshot::pda(snapshot_id).0;
+        let snapshot = Snapshot::try_from(client.get_account_data(&snapshot_pubkey).await?)
+            .map_err(|err| ClientErrorKind::Custom(err.to_string()))?;
+        if snapshot.cumulative_stake == 0 {
+            debug!(
+                "Skipping the network pool, snapshot {} has no stake",
+                snapshot_id
+            );
+            return Ok(());
+        }
+        let mut snapshot_pages = vec![];
+        for id in 0..snapshot.page_count {
+            let data = client
+                .get_account_data(&SnapshotPage::pda(snapshot_pubkey, id).0)
+                .await?;
+            snapshot_pages.push(
+                SnapshotPage::try_from(data)
+                    .map_err(|err| ClientErrorKind::Custom(err.to_string()))?,
+            );
+        }
+
+        // The draw is made from the committed slot's hash, so the sampled node can be found
+        // here and only its page and account passed to the cycle
+        let mut slot_hash = None;
+        for _ in 0..SLOT_HASH_POLLS {
+            let slot_hashes = client.get_account_data(&sysvar::slot_hashes::id()).await?;
+            slot_hash = find_slot_hash(&slot_hashes, commit_slot).map(<[u8]>::to_vec);
+            if slot_hash.is_some() {
+                break;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        let slot_hash = slot_hash.ok_or_else(|| {
+            ClientErrorKind::Custom(format!("The hash of slot {} is not available", commit_slot))
+        })?;
+        let sample = next_nonce(pool.nonce, &slot_hash) % snapshot.cumulative_stake;
+        let (entry_id, entry) = find_snapshot_entry(&snapshot_pages, sample).ok_or_else(|| {
+            ClientErrorKind::Custom(format!("No snapshot entry holds sample {}", sample))
+        })?;
+        let ix = pool_cycle(
+            entry_id,
+            client.identity_pubkey(),
+            NetworkConfig::pda().0,
+            Node::pda(entry.node_authority).0,
+            pool_pubkey,
+            self.registry_address,
+            snapshot_pubkey,
+        );
+        client
+            .sign_and_submit(&[ix], "🎱 Cycling network pool")
+            .await?;
+        Ok(())
+    }
+
+    fn is_delegate(&self) -> bool {
+        let identity = self.client().identity_pubkey();
+        self.delegates.read().unwrap().is_delegate(&identity)
+    }
+
+    fn is_elected(&self) -> bool {
+        let identity = self.client().identity_pubkey();
+        self.delegates.read().unwrap().is_elected(&identity)
+    }
+
+    /// Anchor the cluster clock to the Clock sysvar fetched over RPC.
+    fn anchor_clock(&self) {
+        let client = self.client();
+        let clock = self.clock.clone();
+        self.runtime.spawn(async move {
+            let data = client
+                .get_account_data(&sysvar::clock::id())
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|data| deserialize::<Clock>(&data).map_err(|err| err.to_string()));
+            match data {
+                Ok(sysvar) => clock.observe_clock(&sysvar, Instant::now()),
+                Err(err) => info!("Failed to fetch the clock sysvar: {}", err),
+            }
+        });
+    }
+
+    /// Check the fee payers' balances periodically, so low ones are skipped and reported.
+    fn start_balance_monitor(&self) {
+        let executor = self.clone();
+        self.runtime.spawn(async move {
+            loop {
+                executor.check_balances().await;
+                tokio::select! {
+                    _ = executor.shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(BALANCE_CHECK_INTERVAL) => {}
+                }
+            }
+        });
+    }
+
+    async fn check_balances(&self) {
+        let client = self.client();
+        let balances = match client.check_fee_payer_balances().await {
+            Ok(balances) => balances,
+            Err(err) => {
+                info!("Failed to check fee payer balances: {}", err);
+                return;
+            }
+        };
+        let min_balance = client.fee_payers.min_balance();
+        for (pubkey, lamports) in &balances {
+            if *lamports < min_balance {
+                warn!(
+                    "⚠️ Skipping fee payer {} with {} lamports, below the minimum of {}",
+                    pubkey, lamports, min_balance
+                );
+            }
+        }
+        self.metrics
+            .record_fee_payer_balances(&balances, min_balance);
+    }
+
+    fn start_heartbeat(&self) {
+        let executor = self.clone();
+        self.runtime.spawn(async move {
+            loop {
+                if let Err(err) = executor.heartbeat().await {
+                    info!("Failed to record node heartbeat: {}", err);
+                }
+                tokio::select! {
+                    _ = executor.shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(HEARTBEAT_INTERVAL) => {}
+                }
+            }
+        });
+    }
+
+    async fn heartbeat(&self) -> ClientResult<()> {
+        let client = self.client();
+        let identity = client.identity_pubkey();
+        let node_pubkey = Node::pda(identity).0;
+
+        // Only registered nodes heartbeat. Otherwise every attempt would fail and cost a fee.
+        if !client.account_exists(&node_pubkey).await? {
+            debug!("Skipping node heartbeat, {} is not registered", identity);
+            return Ok(());
+        }
+        let ix = node_heartbeat(identity, node_pubkey);
+        client
+            .sign_and_submit(&[ix], "💓 Recording node heartbeat")
+            .await?;
+        Ok(())
+    }
+
+    /// Execute tasks if the estimated cluster time moved to a new second.
+    fn tick(&self) {
+        if self.shutdown.is_cancelled() {
+            return;
+        }
+        if let Some(unix_timestamp) = self.clock.tick(Instant::now()) {
+            self.execute_tasks_in_lookback_window(unix_timestamp);
+        }
+    }
+
+    /// Keep ticking on a local timer in case slot and Clock updates stop arriving.
+    /// The timer stops once the executor shuts down.
+    fn start_timer(&self) {
+        let executor = self.clone();
+        thread::spawn(move || {
+            let mut last_anchor_attempt = Instant::now();
+            loop {
+                thread::sleep(TICK_INTERVAL);
+                if executor.shutdown.is_cancelled() {
+                    return;
+                }
+
+                // Re-anchor over RPC when no Clock sysvar updates are arriving
+                let now = Instant::now();
+                let is_stale = match executor.clock.anchor_age(now) {
+                    Some(age) => age > REANCHOR_AFTER,
+                    None => true,
+                };
+                if is_stale && now.duration_since(last_anchor_attempt) > REANCHOR_AFTER {
+                    last_anchor_attempt = now;
+                    executor.anchor_clock();
+                }
+
+                executor.tick();
+            }
+        });
+    }
+
+    fn execute_tasks_in_lookback_window(&self, unix_timestamp: i64) {
+        let mut executor = self.clone();
+        executor.latest_clock_value = unix_timestamp;
+        self.runtime.spawn(executor.execute_tasks());
+    }
+
+    async fn execute_tasks(self) {
+        if self.shutdown.is_cancelled() {
+            return;
+        }
+        let settings = self.settings();
+        let config = &settings.config;
+        info!("executing tasks for unix_ts: {}", self.latest_clock_value);
+
+        // Collect the tasks in the lookback window this node is responsible for
+        let node = self.client().identity_pubkey();
+        let lookback_window = config.lookback_window();
+        let crank_authority = *self.crank_authority.read().unwrap();
+        let mut due_tasks = vec![];
+        for t in (self.latest_clock_value - lookback_window)..=self.latest_clock_value {
+            let r_delegates = self.delegates.read().unwrap();
+            if !r_delegates.should_execute(
+                &node,
+                t,
+                self.latest_clock_value,
+                config.backup_grace_period,
+            ) {
+                continue;
+            }
+            let r_cache = self.cache.read().unwrap();
+            r_cache.index.get(&t).and_then(|keys| {
+                for key in keys.iter() {
+                    if r_cache.is_backed_off(key, self.latest_clock_value)
+                        || r_cache.is_executed(key, t)
+                    {
+                        continue;
+                    }
+                    r_cache.data.get(key).and_then(|task| {
+                        // The network's own tasks are executed whatever the node's filters,
+                        // since delegates which miss them are slashed
+                        let balance = r_cache.daemon_balances.get(&task.daemon).copied();
+                        if crank_authority == Some(task.daemon)
+                            || settings.filter.wants_daemon(&task.daemon)
+                                && settings.filter.wants_daemon_balance(balance)
+                        {
+                            due_tasks.push((*key, task.clone()));
+                        }
+                        Some(())
+                    });
+                }
+                Some(())
+            });
+        }
+
+        self.metrics
+            .due_tasks
+            .store(due_tasks.len() as u64, Ordering::Relaxed);
+
+        // Simulate the exec instructions and drop tasks that are certain to fail
+        let workers = settings.workers.clone();
+        let sim_handles: Vec<_> = due_tasks
+            .into_iter()
+            .map(|(key, task)| {
+                let cp_sim = self.clone();
+                let workers = workers.clone();
+                tokio::spawn(async move {
+                    let _permit = workers.acquire_owned().await.unwrap();
+                    let ix = cp_sim.build_exec_ix(key, &task);
+                    match cp_sim.simulate_task(key, &ix).await {
+                        true => Some(((key, task), ix)),
+                        false => None,
+                    }
+                })
+            })
+            .collect();
+        let mut items = vec![];
+        for handle in sim_handles {
+            if let Ok(Some(item)) = handle.await {
+                items.push(item);
+            }
+        }
+
+        // Pack the exec instructions into batches
+        // Size batches for a fee payer other than the identity if there is one, since it adds a
+        // signature to the transaction
+        let identity = settings.client.identity_pubkey();
+        let fee_payer = settings
+            .client
+            .fee_payers
+            .pubkeys()
+            .into_iter()
+            .find(|pubkey| *pubkey != identity)
+            .unwrap_or(identity);
+        let batches = batch::pack(
+            &fee_payer,
+            items,
+            config.max_batch_size(),
+            |(key, _task)| self.task_compute_units(key),
+        );
+
+        // Execute the batches, at most one per worker at a time
+        let handles: Vec<_> = batches
+            .into_iter()
+            .map(|batch| {
+                let cp_batch = self.clone();
+                let workers = workers.clone();
+                tokio::spawn(async move {
+                    let _permit = workers.acquire_owned().await.unwrap();
+                    cp_batch.execute_batch(batch).await;
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.ok();
+        }
+    }
+
+    async fn execute_batch(&self, batch: Vec<((Pubkey, Task), Instruction)>) {
+        // Lock the mutexes for the tasks in this batch
+        let mutexes: Vec<Arc<tokio::sync::Mutex<()>>> = {
+            let mut bucket = self.bucket.lock().unwrap();
+            batch
+                .iter()
+                .map(|((key, task), _ix)| bucket.get_mutex((*key, task.exec_at.unwrap())))
+                .collect()
+        };
+        let guards: Vec<_> = mutexes
+            .into_iter()
+            .map(|m| m.try_lock_owned().ok())
+            .collect();
+
+        // Skip tasks another worker is already executing
+        let batch: Vec<((Pubkey, Task), Instruction)> = batch
+            .into_iter()
+            .zip(guards.iter())
+            .filter(|(_item, guard)| guard.is_some())
+            .map(|(item, _guard)| item)
+            .collect();
+
+        // Track the submissions so executions by other nodes can cancel them,
+        // unless the plugin started shutting down and won't wait on new submissions
+        let keys: Vec<Pubkey> = batch.iter().map(|((key, _), _)| *key).collect();
+        {
+            let mut w_in_flight = self.in_flight.lock().unwrap();
+            if self.shutdown.is_cancelled() {
+                return;
+            }
+            for key in &keys {
+                w_in_flight.start(*key);
+            }
+        }
+        let batch: Vec<((Pubkey, Task), Instruction)> = batch
+            .into_iter()
+            .filter(|((key, _), _)| !self.is_cancelled(key))
+            .collect();
+
+        match batch.len() {
+            0 => {}
+            1 => {
+                let ((key, task), ix) = batch.into_iter().next().unwrap();
+                self.submit_task(key, task, ix).await;
+            }
+            _ => {
+                // Sign and submit the batch
+                let ixs: Vec<Instruction> = batch.iter().map(|(_, ix)| ix.clone()).collect();
+                let metrics = &self.metrics;
+                let start = Instant::now();
+                let batch_keys: Vec<Pubkey> = batch.iter().map(|((key, _), _)| *key).collect();
+                let ixs = self.with_compute_budget(ixs, &batch_keys).await;
+                let res = self
+                    .send_and_confirm(
+                        &ixs,
+                        &batch_keys,
+                        format!("🤖 Executing batch of {} tasks", batch.len()).as_str(),
+                    )
+                    .await;
+
+                match res {
+                    Ok(Confirmation::Confirmed) => {
+                        for _ in 0..batch.len() {
+                            metrics.record_attempt();
+                            metrics.record_success(start.elapsed());
+                        }
+                    }
+                    // A cancelled batch may still land, so resubmitting its tasks could
+                    // execute them twice
+                    Ok(Confirmation::Cancelled) => {
+                        for _ in 0..batch.len() {
+                            metrics.record_attempt();
+                        }
+                    }
+                    Err(err) => {
+                        // Fall back to one transaction per task so a bad task can't block the others
+                        info!("❌ Batch failed, retrying tasks individually: {}", err);
+                        for ((key, task), ix) in batch {
+                            self.submit_task(key, task, ix).await;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Stop tracking the submissions and drop the mutexes
+        let mut w_in_flight = self.in_flight.lock().unwrap();
+        for key in &keys {
+            w_in_flight.finish(key);
+        }
+        drop(guards)
+    }
+
+    async fn submit_task(&self, key: Pubkey, task: Task, ix: Instruction) {
+        if self.is_cancelled(&key) {
+            return;
+        }
+
+        // Sign and submit
+        let metrics = &self.metrics;
+        metrics.record_attempt();
+        let start = Instant::now();
+        let ixs = self.with_compute_budget(vec![ix], &[key]).await;
+        let retry = self.config().retry_policy();
+        let mut attempt = 1;
+        let res = loop {
+            let res = self
+                .send_and_confirm(
+                    &ixs,
+                    &[key],
+                    format!("🤖 Executing task: {} {}", key, task.exec_at.unwrap()).as_str(),
+                )
+                .await;
+            match &res {
+                Err(err) if retry.should_retry(attempt, err) && !self.is_cancelled(&key) => {
+                    info!("Retrying task {} after attempt {}: {}", key, attempt, err);
+                    tokio::time::sleep(retry.delay()).await;
+                    attempt += 1;
+                }
+                _ => break res,
+            }
+        };
+        match &res {
+            Ok(Confirmation::Confirmed) => {
+                metrics.record_success(start.elapsed());
+                self.cache.write().unwrap().clear_backoff(&key);
+            }
+            // Another node executed the task, which is counted as a preemption,
+            // or the plugin is shutting down
+            Ok(Confirmation::Cancelled) => {}
+            Err(err) => metrics.record_failure(err, start.elapsed()),
+        }
+
+        // If exec failed, replicate the task data
+        if let Err(err) = res {
+            info!("❌ {}", err);
+            match self.client().get_account_data(&key).await {
+                Ok(data) => match Task::try_from(data) {
+                    Ok(task) => self.replicate_task(key, task),
+                    Err(err) => info!("Failed to deserialize task {}: {}", key, err),
+                },
+                Err(err) => info!("Failed to fetch task {}: {}", key, err),
+            }
+        }
+    }
+
+    /// Send a transaction and poll its status until it is confirmed,
+    /// or until another node executes one of its tasks.
+    /// Nothing new is sent once the plugin is shutting down.
+    async fn send_and_confirm(
+        &self,
+        ixs: &[Instruction],
+        keys: &[Pubkey],
+        memo: &str,
+    ) -> ClientResult<Confirmation> {
+        if self.shutdown.is_cancelled() {
+            return Ok(Confirmation::Cancelled);
+        }
+        let client = self.client();
+        let signature = client.sign_and_send(ixs, memo).await?;
+        client
+            .confirm(&signature, CONFIRM_TIMEOUT, || {
+                keys.iter().any(|key| self.is_cancelled(key))
+            })
+            .await
+    }
+
+    /// Simulate a task's exec instruction, returning whether it is worth submitting.
+    async fn simulate_task(&self, key: Pubkey, ix: &Instruction) -> bool {
+        let mut ixs = budget::compute_budget_ixs(Some(MAX_TX_COMPUTE_UNITS), None);
+        ixs.push(ix.clone());
+
+        let res = match self.client().simulate(&ixs).await {
+            Ok(res) => res,
+            Err(err) => {
+                info!("Failed to simulate task {}: {}", key, err);
+                return true;
+            }
+        };
+
+        let outcome = simulation::parse(
+            res.err.as_ref(),
+            res.logs.as_deref().unwrap_or_default(),
+            res.units_consumed,
+            &cronos_sdk::SCHEDULER_PROGRAM_ID,
+        );
+        match outcome {
+            SimulationOutcome::Success { units_consumed } => {
+                let mut w_cache = self.cache.write().unwrap();
+                if let Some(units) = units_consumed {
+                    w_cache.record_compute_units(key, units);
+                }
+                true
+            }
+            SimulationOutcome::NotDue => false,
+            SimulationOutcome::Failed { reason } => {
+                info!("⏸ Backing off task {}: {}", key, reason);
+                self.metrics.record_skip();
+                let mut w_cache = self.cache.write().unwrap();
+                w_cache.back_off(key, self.latest_clock_value);
+                false
+            }
+            SimulationOutcome::Inconclusive { reason } => {
+                info!("Inconclusive simulation for task {}: {}", key, reason);
+                true
+            }
+        }
+    }
+
+    /// Compute units to request for a task's exec instruction.
+    fn task_compute_units(&self, key: &Pubkey) -> u64 {
+        match &self.config().compute_unit_limit {
+            Some(limit @ ComputeUnitLimit::Fixed { .. }) => limit.task_units(),
+            Some(limit) => {
+                let r_cache = self.cache.read().unwrap();
+                r_cache
+                    .compute_units
+                    .get(key)
+                    .map_or(limit.task_units(), |units| limit.with_margin(*units))
+            }
+            None => DEFAULT_TASK_COMPUTE_UNITS,
+        }
+    }
+
+    async fn with_compute_budget(
+        &self,
+        ixs: Vec<Instruction>,
+        keys: &[Pubkey],
+    ) -> Vec<Instruction> {
+        let config = self.config();
+        let client = self.client();
+
+        // Size the compute unit limit
+        let units = config.compute_unit_limit.as_ref().map(|_limit| {
+            keys.iter()
+                .map(|key| self.task_compute_units(key))
+                .sum::<u64>()
+        });
+
+        // Price the compute units
+        let micro_lamports = match &config.priority_fee {
+            None => None,
+            Some(PriorityFee::Fixed { micro_lamports }) => Some(*micro_lamports),
+            Some(PriorityFee::Percentile {
+                percentile,
+                max_micro_lamports,
+                fallback_micro_lamports,
+            }) => {
+                let accounts: Vec<Pubkey> = ixs
+                    .iter()
+                    .flat_map(|ix| ix.accounts.iter())
+                    .filter(|acc| acc.is_writable)
+                    .map(|acc| acc.pubkey)
+                    .collect();
+                match client.recent_prioritization_fees(&accounts).await {
+                    Ok(Some(fees)) => {
+                        let fee = budget::fee_percentile(fees, *percentile);
+                        Some(max_micro_lamports.map_or(fee, |max| fee.min(max)))
+                    }
+                    Ok(None) => Some(*fallback_micro_lamports),
+                    Err(err) => {
+                        info!("Failed to fetch recent prioritization fees: {}", err);
+                        Some(*fallback_micro_lamports)
+                    }
+                }
+            }
+        };
+
+        let mut budget_ixs = budget::compute_budget_ixs(units, micro_lamports);
+        budget_ixs.extend(ixs);
+        budget_ixs
+    }
+
+    fn build_exec_ix(&self, key: Pubkey, task: &Task) -> Instruction {
+        // Get accounts
+        let config = cronos_sdk::scheduler::state::Config::pda().0;
+        let fee = Fee::pda(task.daemon).0;
+        let pool = self.delegates.read().unwrap().address;
+
+        // Add accounts to exec instruction
+        let mut ix_exec = cronos_sdk::scheduler::instruction::task_exec(
+            self.client().identity_pubkey(),
+            config,
+            task.daemon,
+            fee,
+            pool,
+            key,
+        );
+
+        // Add the accounts and programs of every task instruction
+        ix_exec
+            .accounts
+            .extend(resolve_accounts(&task.daemon, &task.ixs));
+
+        ix_exec
+    }
+}
@@ -7,26 +7,55 @@ use {
 #[derive(Clone)]
 pub struct Filter {
     program_includes: HashSet<[u8; 32]>,
+    program_excludes: HashSet<[u8; 32]>,
+    daemon_includes: HashSet<Pubkey>,
+    daemon_excludes: HashSet<Pubkey>,
+    min_daemon_balance: u64,
 }
 
 impl Filter {
     pub fn new(config: &Config) -> Self {
         Self {
-            program_includes: config
-                .program_includes
-                .iter()
-                .flat_map(|p| Pubkey::from_str(p).ok().map(|p| p.to_bytes()))
+            program_includes: parse_keys(&config.program_includes)
+                .map(|p| p.to_bytes())
                 .collect(),
+            program_excludes: parse_keys(&config.program_excludes)
+                .map(|p| p.to_bytes())
+                .collect(),
+            daemon_includes: parse_keys(&config.daemon_includes).collect(),
+            daemon_excludes: parse_keys(&config.daemon_excludes).collect(),
+            min_daemon_balance: config.min_daemon_balance.unwrap_or(0),
         }
     }
 
+    /// Whether to replicate accounts owned by the given program.
+    /// Excludes take precedence over includes.
     pub fn wants_program(&self, program: &[u8]) -> bool {
         let key = match <&[u8; 32]>::try_from(program) {
             Ok(key) => key,
-            _ => return true,
+            _ => return false,
         };
-        self.program_includes.contains(key)
+        !self.program_excludes.contains(key) && self.program_includes.contains(key)
+    }
+
+    /// Whether to service tasks of the given daemon. An empty include list allows every daemon.
+    pub fn wants_daemon(&self, daemon: &Pubkey) -> bool {
+        !self.daemon_excludes.contains(daemon)
+            && (self.daemon_includes.is_empty() || self.daemon_includes.contains(daemon))
     }
+
+    /// Whether a daemon holds enough lamports to be serviced.
+    /// Daemons with an unknown balance are serviced and left for simulation to catch.
+    pub fn wants_daemon_balance(&self, balance: Option<u64>) -> bool {
+        match balance {
+            Some(balance) => balance >= self.min_daemon_balance,
+            None => true,
+        }
+    }
+}
+
+fn parse_keys(keys: &[String]) -> impl Iterator<Item = Pubkey> + '_ {
+    keys.iter().flat_map(|k| Pubkey::from_str(k).ok())
 }
 
 #[cfg(test)]
@@ -55,4 +84,61 @@ mod tests {
                 .to_bytes()
         ));
     }
+
+    #[test]
+    fn test_filter_excludes() {
+        let config = Config {
+            program_includes: vec!["CronpZj5NbHj2Nb6WwEtf6A9anty9JfEQ1RnGoshQBaW".to_owned()],
+            program_excludes: vec!["CronpZj5NbHj2Nb6WwEtf6A9anty9JfEQ1RnGoshQBaW".to_owned()],
+            ..Config::default()
+        };
+
+        let filter = Filter::new(&config);
+        assert!(!filter.wants_program(
+            &Pubkey::from_str("CronpZj5NbHj2Nb6WwEtf6A9anty9JfEQ1RnGoshQBaW")
+                .unwrap()
+                .to_bytes()
+        ));
+
+        // Malformed owners are never wanted
+        assert!(!filter.wants_program(&[0; 31]));
+    }
+
+    #[test]
+    fn test_filter_daemons() {
+        let allowed = Pubkey::new_unique();
+        let denied = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        let filter = Filter::new(&Config {
+            daemon_excludes: vec![denied.to_string()],
+            ..Config::default()
+        });
+        assert!(filter.wants_daemon(&allowed));
+        assert!(filter.wants_daemon(&other));
+        assert!(!filter.wants_daemon(&denied));
+
+        let filter = Filter::new(&Config {
+            daemon_includes: vec![allowed.to_string(), denied.to_string()],
+            daemon_excludes: vec![denied.to_string()],
+            ..Config::default()
+        });
+        assert!(filter.wants_daemon(&allowed));
+        assert!(!filter.wants_daemon(&other));
+        assert!(!filter.wants_daemon(&denied));
+    }
+
+    #[test]
+    fn test_filter_daemon_balance() {
+        let filter = Filter::new(&Config::default());
+        assert!(filter.wants_daemon_balance(Some(0)));
+
+        let filter = Filter::new(&Config {
+            min_daemon_balance: Some(1_000_000),
+            ..Config::default()
+        });
+        assert!(filter.wants_daemon_balance(None));
+        assert!(filter.wants_daemon_balance(Some(1_000_000)));
+        assert!(!filter.wants_daemon_balance(Some(999_999)));
+    }
 }
@@ -1,16 +1,36 @@
-use {
-    dotenv::dotenv,
-    solana_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin,
-};
+use {dotenv::dotenv, solana_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin};
 
+mod batch;
 mod bucket;
+mod budget;
 mod cache;
 mod client;
+mod clock;
 mod config;
+mod delegates;
+mod executions;
+mod executor;
 mod filter;
+mod metrics;
+mod payers;
 mod plugin;
+mod resolver;
+mod settings;
+mod simulation;
 
-pub use {bucket::Bucket, cache::TaskCache, config::Config, filter::Filter, plugin::CronosPlugin};
+pub use {
+    bucket::Bucket,
+    cache::TaskCache,
+    client::{AsyncClient, Confirmation},
+    config::Config,
+    delegates::Delegates,
+    executor::{Executor, ExecutorError},
+    filter::Filter,
+    metrics::Metrics,
+    payers::FeePayers,
+    plugin::CronosPlugin,
+    settings::Settings,
+};
 
 #[no_mangle]
 #[allow(improper_ctypes_definitions)]
@@ -0,0 +1,333 @@
+use {
+    log::{info, warn},
+    solana_client::client_error::{ClientError, ClientErrorKind},
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::HashMap,
+        fmt::Write as FmtWrite,
+        io::{BufRead, BufReader, Write},
+        net::{SocketAddr, TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicI64, AtomicU64, Ordering},
+            Arc, RwLock,
+        },
+        thread,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Operational counters exported by the plugin in the Prometheus text format.
+#[derive(Default)]
+pub struct Metrics {
+    pub cached_tasks: AtomicU64,
+    pub due_tasks: AtomicU64,
+    pub executions_attempted: AtomicU64,
+    pub executions_succeeded: AtomicU64,
+    pub executions_failed: RwLock<HashMap<&'static str, u64>>,
+    pub executions_skipped: AtomicU64,
+    pub executions_preempted: AtomicU64,
+    pub confirm_latency_ms_sum: AtomicU64,
+    pub confirm_latency_count: AtomicU64,
+    pub submit_latency_ms_sum: AtomicU64,
+    pub submit_latency_count: AtomicU64,
+    pub latest_clock: AtomicI64,
+    pub fee_payer_balances: RwLock<Vec<(Pubkey, u64)>>,
+    pub low_balance_fee_payers: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Metrics {
+        Metrics::default()
+    }
+
+    pub fn record_attempt(&self) {
+        self.executions_attempted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_skip(&self) {
+        self.executions_skipped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_preempted(&self) {
+        self.executions_preempted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the time from submitting a task until its transaction was seen landing.
+    pub fn record_confirmation(&self, latency: Duration) {
+        self.confirm_latency_ms_sum
+            .fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+        self.confirm_latency_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_success(&self, latency: Duration) {
+        self.executions_succeeded.fetch_add(1, Ordering::Relaxed);
+        self.record_latency(latency);
+    }
+
+    pub fn record_failure(&self, err: &ClientError, latency: Duration) {
+        let mut w_failed = self.executions_failed.write().unwrap();
+        *w_failed.entry(error_class(err)).or_insert(0) += 1;
+        self.record_latency(latency);
+    }
+
+    pub fn record_fee_payer_balances(&self, balances: &[(Pubkey, u64)], min_balance: u64) {
+        let low = balances.iter().filter(|(_, l)| *l < min_balance).count();
+        self.low_balance_fee_payers
+            .store(low as u64, Ordering::Relaxed);
+        *self.fee_payer_balances.write().unwrap() = balances.to_vec();
+    }
+
+    fn record_latency(&self, latency: Duration) {
+        self.submit_latency_ms_sum
+            .fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+        self.submit_latency_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let latest_clock = self.latest_clock.load(Ordering::Relaxed);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        let clock_lag = if latest_clock > 0 {
+            now - latest_clock
+        } else {
+            0
+        };
+
+        gauge(
+            &mut out,
+            "cronos_cached_tasks",
+            "Number of tasks held in the plugin cache",
+            self.cached_tasks.load(Ordering::Relaxed),
+        );
+        gauge(
+            &mut out,
+            "cronos_due_tasks",
+            "Number of tasks due within the lookback window",
+            self.due_tasks.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "cronos_executions_attempted_total",
+            "Task executions submitted",
+            self.executions_attempted.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "cronos_executions_succeeded_total",
+            "Task executions confirmed",
+            self.executions_succeeded.load(Ordering::Relaxed),
+        );
+
+        counter(
+            &mut out,
+            "cronos_executions_skipped_total",
+            "Task executions skipped because simulation predicted a failure",
+            self.executions_skipped.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "cronos_executions_preempted_total",
+            "Task submissions cancelled because another node executed the task first",
+            self.executions_preempted.load(Ordering::Relaxed),
+        );
+
+        writeln!(
+            out,
+            "# HELP cronos_executions_failed_total Task executions that failed, by error class"
+        )
+        .unwrap();
+        writeln!(out, "# TYPE cronos_executions_failed_total counter").unwrap();
+        let r_failed = self.executions_failed.read().unwrap();
+        let mut classes: Vec<_> = r_failed.iter().collect();
+        classes.sort();
+        for (class, count) in classes {
+            writeln!(
+                out,
+                "cronos_executions_failed_total{{class=\"{}\"}} {}",
+                class, count
+            )
+            .unwrap();
+        }
+
+        writeln!(
+            out,
+            "# HELP cronos_submit_latency_ms Time spent signing and confirming task transactions"
+        )
+        .unwrap();
+        writeln!(out, "# TYPE cronos_submit_latency_ms summary").unwrap();
+        writeln!(
+            out,
+            "cronos_submit_latency_ms_sum {}",
+            self.submit_latency_ms_sum.load(Ordering::Relaxed)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "cronos_submit_latency_ms_count {}",
+            self.submit_latency_count.load(Ordering::Relaxed)
+        )
+        .unwrap();
+
+        writeln!(
+            out,
+            "# HELP cronos_confirm_latency_ms Time from submitting a task until its transaction landed"
+        )
+        .unwrap();
+        writeln!(out, "# TYPE cronos_confirm_latency_ms summary").unwrap();
+        writeln!(
+            out,
+            "cronos_confirm_latency_ms_sum {}",
+            self.confirm_latency_ms_sum.load(Ordering::Relaxed)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "cronos_confirm_latency_ms_count {}",
+            self.confirm_latency_count.load(Ordering::Relaxed)
+        )
+        .unwrap();
+
+        writeln!(
+            out,
+            "# HELP cronos_fee_payer_balance_lamports Balance of each fee payer at the last check"
+        )
+        .unwrap();
+        writeln!(out, "# TYPE cronos_fee_payer_balance_lamports gauge").unwrap();
+        for (pubkey, lamports) in self.fee_payer_balances.read().unwrap().iter() {
+            writeln!(
+                out,
+                "cronos_fee_payer_balance_lamports{{payer=\"{}\"}} {}",
+                pubkey, lamports
+            )
+            .unwrap();
+        }
+        gauge(
+            &mut out,
+            "cronos_fee_payers_low",
+            "Number of fee payers skipped because their balance is below the minimum",
+            self.low_balance_fee_payers.load(Ordering::Relaxed),
+        );
+
+        gauge(
+            &mut out,
+            "cronos_clock_unix_timestamp",
+            "Latest Clock sysvar unix_timestamp seen by the plugin",
+            latest_clock,
+        );
+        gauge(
+            &mut out,
+            "cronos_clock_lag_seconds",
+            "Wall clock time minus the latest Clock sysvar value",
+            clock_lag,
+        );
+        out
+    }
+}
+
+/// Bind a local listener and serve the metrics on a background thread.
+/// Returns the bound address, which is useful when binding to port 0.
+pub fn serve(addr: &str, metrics: Arc<Metrics>) -> std::io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    info!("Serving metrics on http://{}/metrics", local_addr);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(err) = respond(stream, &metrics) {
+                        warn!("Failed to serve metrics: {}", err);
+                    }
+                }
+                Err(err) => warn!("Failed to accept metrics connection: {}", err),
+            }
+        }
+    });
+
+    Ok(local_addr)
+}
+
+fn respond(mut stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+    // Drain the request head; every path serves the same payload.
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+
+    let body = metrics.render();
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn error_class(err: &ClientError) -> &'static str {
+    match err.kind() {
+        ClientErrorKind::TransactionError(_) => "transaction",
+        ClientErrorKind::RpcError(_) => "rpc",
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => "network",
+        ClientErrorKind::SigningError(_) => "signing",
+        _ => "other",
+    }
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: impl std::fmt::Display) {
+    writeln!(out, "# HELP {} {}", name, help).unwrap();
+    writeln!(out, "# TYPE {} gauge", name).unwrap();
+    writeln!(out, "{} {}", name, value).unwrap();
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    writeln!(out, "# HELP {} {}", name, help).unwrap();
+    writeln!(out, "# TYPE {} counter", name).unwrap();
+    writeln!(out, "{} {}", name, value).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::transaction::TransactionError, std::io::Read};
+
+    #[test]
+    fn test_scrape() {
+        let metrics = Arc::new(Metrics::new());
+        metrics.cached_tasks.store(3, Ordering::Relaxed);
+        metrics.record_attempt();
+        metrics.record_attempt();
+        metrics.record_success(Duration::from_millis(40));
+        metrics.record_failure(
+            &ClientError::from(TransactionError::AccountNotFound),
+            Duration::from_millis(10),
+        );
+        let payer = Pubkey::new_unique();
+        metrics.record_fee_payer_balances(&[(payer, 5), (Pubkey::new_unique(), 50)], 10);
+
+        let addr = serve("127.0.0.1:0", metrics).unwrap();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut res = String::new();
+        stream.read_to_string(&mut res).unwrap();
+
+        assert!(res.starts_with("HTTP/1.1 200 OK"));
+        assert!(res.contains("cronos_cached_tasks 3"));
+        assert!(res.contains("cronos_executions_attempted_total 2"));
+        assert!(res.contains("cronos_executions_succeeded_total 1"));
+        assert!(res.contains("cronos_executions_failed_total{class=\"transaction\"} 1"));
+        assert!(res.contains("cronos_submit_latency_ms_sum 50"));
+        assert!(res.contains("cronos_submit_latency_ms_count 2"));
+        assert!(res.contains(&format!(
+            "cronos_fee_payer_balance_lamports{{payer=\"{}\"}} 5",
+            payer
+        )));
+        assert!(res.contains("cronos_fee_payers_low 1"));
+    }
+}
@@ -0,0 +1,134 @@
+use {
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+    std::sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+/// Balance assumed for a payer until it is first checked.
+const UNKNOWN_BALANCE: u64 = u64::MAX;
+
+/// Keypairs which take turns paying transaction fees.
+///
+/// Payers whose balance falls below the minimum are skipped until they are topped up.
+pub struct FeePayers {
+    keypairs: Vec<Keypair>,
+    balances: Vec<AtomicU64>,
+    next: AtomicUsize,
+    min_balance: u64,
+}
+
+impl FeePayers {
+    pub fn new(keypairs: Vec<Keypair>, min_balance: u64) -> Self {
+        Self {
+            balances: keypairs
+                .iter()
+                .map(|_| AtomicU64::new(UNKNOWN_BALANCE))
+                .collect(),
+            keypairs,
+            next: AtomicUsize::new(0),
+            min_balance,
+        }
+    }
+
+    pub fn pubkeys(&self) -> Vec<Pubkey> {
+        self.keypairs.iter().map(|k| k.pubkey()).collect()
+    }
+
+    pub fn contains(&self, pubkey: &Pubkey) -> bool {
+        self.keypairs.iter().any(|k| k.pubkey() == *pubkey)
+    }
+
+    pub fn min_balance(&self) -> u64 {
+        self.min_balance
+    }
+
+    /// The next payer with enough balance, in round-robin order.
+    pub fn next(&self) -> Option<&Keypair> {
+        let len = self.keypairs.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..len)
+            .map(|i| start.wrapping_add(i) % len)
+            .find(|i| !self.is_low(*i))
+            .map(|i| &self.keypairs[i])
+    }
+
+    /// Carry over the known balances of payers which are also in `previous`, so a reloaded
+    /// config doesn't fall back to low payers until their balances are checked again.
+    pub fn keep_balances(&self, previous: &FeePayers) {
+        for (keypair, balance) in self.keypairs.iter().zip(&self.balances) {
+            if let Some(index) = previous
+                .keypairs
+                .iter()
+                .position(|k| k.pubkey() == keypair.pubkey())
+            {
+                balance.store(
+                    previous.balances[index].load(Ordering::Relaxed),
+                    Ordering::Relaxed,
+                );
+            }
+        }
+    }
+
+    /// Record a payer's balance, returning whether it is below the minimum.
+    pub fn update_balance(&self, index: usize, lamports: u64) -> bool {
+        self.balances[index].store(lamports, Ordering::Relaxed);
+        self.is_low(index)
+    }
+
+    fn is_low(&self, index: usize) -> bool {
+        self.balances[index].load(Ordering::Relaxed) < self.min_balance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin() {
+        let payers = FeePayers::new(vec![Keypair::new(), Keypair::new(), Keypair::new()], 10);
+        let pubkeys = payers.pubkeys();
+        let picked: Vec<Pubkey> = (0..4).map(|_| payers.next().unwrap().pubkey()).collect();
+        assert_eq!(picked, vec![pubkeys[0], pubkeys[1], pubkeys[2], pubkeys[0]]);
+    }
+
+    #[test]
+    fn test_skips_low_balances() {
+        let payers = FeePayers::new(vec![Keypair::new(), Keypair::new()], 10);
+        let pubkeys = payers.pubkeys();
+        assert!(payers.update_balance(0, 9));
+        assert!(!payers.update_balance(1, 10));
+        assert!((0..3).all(|_| payers.next().unwrap().pubkey() == pubkeys[1]));
+
+        // Every payer is low
+        assert!(payers.update_balance(1, 0));
+        assert!(payers.next().is_none());
+
+        // Topped up payers come back
+        assert!(!payers.update_balance(0, 1_000));
+        assert_eq!(payers.next().unwrap().pubkey(), pubkeys[0]);
+    }
+
+    #[test]
+    fn test_keep_balances() {
+        let kept = Keypair::new();
+        let previous = FeePayers::new(
+            vec![
+                Keypair::from_bytes(&kept.to_bytes()).unwrap(),
+                Keypair::new(),
+            ],
+            10,
+        );
+        assert!(previous.update_balance(0, 9));
+        assert!(!previous.update_balance(1, 10));
+
+        // The low payer is still skipped after a reload, and new payers are unknown
+        let added = Keypair::new();
+        let added_pubkey = added.pubkey();
+        let payers = FeePayers::new(vec![kept, added], 10);
+        payers.keep_balances(&previous);
+        assert!((0..3).all(|_| payers.next().unwrap().pubkey() == added_pubkey));
+    }
+}
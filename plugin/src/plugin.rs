@@ -1,32 +1,21 @@
 use {
-    crate::{client::RPCClient, Bucket, Config, Filter, TaskCache},
-    bincode::deserialize,
-    cronos_sdk::scheduler::state::{AccountMetaData, Fee, Task},
+    crate::{executions, executor::Executor, settings::Settings},
     log::{debug, info},
     solana_geyser_plugin_interface::geyser_plugin_interface::{
         GeyserPlugin, GeyserPluginError as PluginError, ReplicaAccountInfo,
-        ReplicaAccountInfoVersions, Result as PluginResult,
-    },
-    solana_client_helpers::Client,
-    solana_program::{clock::Clock, pubkey::Pubkey, sysvar},
-    solana_sdk::instruction::AccountMeta,
-    std::{
-        collections::HashMap,
-        fmt::{Debug, Formatter},
-        sync::Mutex,
-        sync::{Arc, RwLock},
-        thread::{self, JoinHandle},
+        ReplicaAccountInfoVersions, ReplicaTransactionInfoVersions, Result as PluginResult,
+        SlotStatus,
     },
+    solana_program::pubkey::Pubkey,
+    std::fmt::{Debug, Formatter},
     thiserror::Error,
+    tokio::runtime::{Builder, Runtime},
 };
 
-#[derive(Clone)]
+/// Geyser plugin which feeds validator updates to an executor.
 pub struct CronosPlugin {
-    client: Option<Arc<Client>>,
-    cache: Option<Arc<RwLock<TaskCache>>>,
-    bucket: Option<Arc<Mutex<Bucket>>>,
-    filter: Option<Filter>,
-    latest_clock_value: i64,
+    executor: Option<Executor>,
+    runtime: Option<Runtime>,
 }
 
 impl Debug for CronosPlugin {
@@ -37,14 +26,8 @@ impl Debug for CronosPlugin {
 
 #[derive(Error, Debug)]
 pub enum CronosPluginError {
-    #[error("Error reading and/or writing to local cache. Error message: ({msg})")]
-    CacheError { msg: String },
-
-    #[error("Error deserializing task data")]
-    TaskAccountInfoError,
-
-    #[error("Error deserializing sysvar clock data")]
-    ClockAccountInfoError,
+    #[error("Error starting the async runtime. Error message: ({msg})")]
+    RuntimeError { msg: String },
 }
 
 impl GeyserPlugin for CronosPlugin {
@@ -53,27 +36,41 @@ impl GeyserPlugin for CronosPlugin {
     }
 
     fn on_load(&mut self, config_file: &str) -> PluginResult<()> {
-        solana_logger::setup_with_default("info");
+        let settings = Settings::load(config_file)?;
+        solana_logger::setup_with_default(settings.config.log_level());
 
         info!("Loading plugin {:?}", self.name());
 
-        let config = Config::read_from(config_file)?;
-
-        self.filter = Some(Filter::new(&config));
-        self.bucket = Some(Arc::new(Mutex::new(Bucket::new())));
-        self.cache = Some(Arc::new(RwLock::new(TaskCache::new())));
-        self.client = Some(Arc::new(Client::new(config.keypath, config.rpc_url)));
-        self.latest_clock_value = 0;
+        // All network requests run on this runtime so geyser callbacks never block on them
+        let runtime = Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("cronos-plugin")
+            .build()
+            .map_err(|e| {
+                PluginError::Custom(Box::new(CronosPluginError::RuntimeError {
+                    msg: e.to_string(),
+                }))
+            })?;
+        let executor = Executor::new(config_file, settings, runtime.handle().clone())
+            .map_err(|e| PluginError::Custom(Box::new(e)))?;
+
+        self.executor = Some(executor);
+        self.runtime = Some(runtime);
         Ok(())
     }
 
     fn on_unload(&mut self) {
         info!("Unloading plugin: {:?}", self.name());
 
-        self.bucket = None;
-        self.cache = None;
-        self.client = None;
-        self.filter = None;
+        // Stop starting new work, then let submissions that were already sent confirm
+        if let Some(executor) = self.executor.take() {
+            executor.shutdown();
+        }
+
+        // Stop the runtime, abandoning anything that didn't drain in time
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
     }
 
     fn update_account(
@@ -82,65 +79,21 @@ impl GeyserPlugin for CronosPlugin {
         slot: u64,
         is_startup: bool,
     ) -> PluginResult<()> {
-        if is_startup {
-            return Ok(());
-        }
+        let executor = match self.active_executor() {
+            Some(executor) if !is_startup => executor,
+            _ => return Ok(()),
+        };
 
         let info = Self::unwrap_update_account(account);
-
-        if !self.unwrap_filter().wants_program(info.owner) {
-            return Ok(());
-        }
-
-        debug!(
-            "Updating account {:?} with owner {:?} at slot {:?}",
-            info.pubkey, info.owner, slot
-        );
-
-        match &mut self.cache {
-            None => {
-                return Err(PluginError::Custom(Box::new(
-                    CronosPluginError::CacheError {
-                        msg: "There is no available cache to update account data".to_string(),
-                    },
-                )));
-            }
-            Some(_cache) => {
-                if &sysvar::clock::id().to_bytes() == info.pubkey {
-                    let clock = deserialize::<Clock>(info.data);
-
-                    match clock {
-                        Err(_err) => {
-                            return Err(PluginError::Custom(Box::new(
-                                CronosPluginError::ClockAccountInfoError,
-                            )))
-                        }
-                        Ok(clock) => {
-                            if self.latest_clock_value < clock.unix_timestamp {
-                                self.latest_clock_value = clock.unix_timestamp;
-                                self.execute_tasks_in_lookback_window();
-                            }
-                        }
-                    }
-                } else if &cronos_sdk::SCHEDULER_PROGRAM_ID.to_bytes() == info.owner {
-                    let task = Task::try_from(info.data.to_vec());
-                    let key = Pubkey::new(info.pubkey);
-
-                    match task {
-                        Err(_err) => {
-                            return Err(PluginError::Custom(Box::new(
-                                CronosPluginError::TaskAccountInfoError,
-                            )))
-                        }
-                        Ok(task) => {
-                            self.replicate_task(key, task);
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(())
+        debug!("Account update {:?} at slot {:?}", info.pubkey, slot);
+        executor
+            .update_account(
+                Pubkey::new(info.pubkey),
+                &Pubkey::new(info.owner),
+                info.lamports,
+                info.data,
+            )
+            .map_err(|e| PluginError::Custom(Box::new(e)))
     }
 
     fn notify_end_of_startup(&mut self) -> PluginResult<()> {
@@ -149,18 +102,50 @@ impl GeyserPlugin for CronosPlugin {
 
     fn update_slot_status(
         &mut self,
-        _slot: u64,
+        slot: u64,
         _parent: Option<u64>,
-        _status: solana_geyser_plugin_interface::geyser_plugin_interface::SlotStatus,
+        status: SlotStatus,
     ) -> PluginResult<()> {
+        let executor = match self.active_executor() {
+            Some(executor) => executor,
+            None => return Ok(()),
+        };
+        match status {
+            SlotStatus::Confirmed | SlotStatus::Rooted => executor.update_slot(slot),
+            SlotStatus::Processed => {}
+        }
         Ok(())
     }
 
     fn notify_transaction(
         &mut self,
-        _transaction: solana_geyser_plugin_interface::geyser_plugin_interface::ReplicaTransactionInfoVersions,
+        transaction: ReplicaTransactionInfoVersions,
         _slot: u64,
     ) -> PluginResult<()> {
+        let executor = match self.active_executor() {
+            Some(executor) => executor,
+            None => return Ok(()),
+        };
+        let info = match transaction {
+            ReplicaTransactionInfoVersions::V0_0_1(info) => info,
+        };
+        if info.is_vote || info.transaction_status_meta.status.is_err() {
+            return Ok(());
+        }
+
+        // Only successful task executions are of interest
+        let scheduler_program_id = cronos_sdk::SCHEDULER_PROGRAM_ID;
+        let message = info.transaction.message();
+        if !message
+            .program_instructions_iter()
+            .any(|(program_id, _ix)| *program_id == scheduler_program_id)
+        {
+            return Ok(());
+        }
+        let tasks = executions::executed_tasks(info.transaction, &scheduler_program_id);
+        if !tasks.is_empty() {
+            executor.update_executions(message.fee_payer(), &tasks);
+        }
         Ok(())
     }
 
@@ -176,162 +161,29 @@ impl GeyserPlugin for CronosPlugin {
     }
 
     fn transaction_notifications_enabled(&self) -> bool {
-        false
+        true
     }
 }
 
 impl CronosPlugin {
     pub fn new() -> Self {
         Self {
-            cache: None,
-            client: None,
-            bucket: None,
-            filter: None,
-            latest_clock_value: 0,
+            executor: None,
+            runtime: None,
         }
     }
-    fn unwrap_bucket(&self) -> &Arc<Mutex<Bucket>> {
-        self.bucket.as_ref().expect("client is unavailable")
-    }
-    fn unwrap_cache(&self) -> &Arc<RwLock<TaskCache>> {
-        self.cache.as_ref().expect("cache is unavailable")
-    }
-    fn unwrap_client(&self) -> &Arc<Client> {
-        self.client.as_ref().expect("client is unavailable")
-    }
-    fn unwrap_filter(&self) -> &Filter {
-        self.filter.as_ref().expect("filter is unavailable")
+
+    /// The executor, unless the plugin isn't loaded or is shutting down.
+    /// Callbacks are ignored while there is none.
+    fn active_executor(&self) -> Option<&Executor> {
+        self.executor
+            .as_ref()
+            .filter(|executor| !executor.is_shutting_down())
     }
+
     fn unwrap_update_account(account: ReplicaAccountInfoVersions) -> &ReplicaAccountInfo {
         match account {
             ReplicaAccountInfoVersions::V0_0_1(info) => info,
         }
     }
-
-    fn replicate_task(&self, key: Pubkey, task: Task) {
-        info!("💽 Replicating task {}", key);
-        let mut w_cache = self.unwrap_cache().write().unwrap();
-        match task.exec_at {
-            Some(_t) => w_cache.insert(key, task),
-            None => w_cache.delete(key),
-        }
-    }
-
-    fn execute_tasks_in_lookback_window(&self) {
-        let self_clone = self.clone();
-        let cp_arc: Arc<CronosPlugin> = Arc::new(self_clone);
-        let cp_clone = cp_arc.clone();
-
-        thread::spawn(move || {
-            const LOOKBACK_WINDOW: i64 = 60 * 15; // Number of seconds to lookback
-            info!(
-                "executing tasks for unix_ts: {}",
-                cp_clone.latest_clock_value
-            );
-
-            // Spawn threads to execute tasks in lookback window
-            let mut handles = vec![];
-            for t in (cp_clone.latest_clock_value - LOOKBACK_WINDOW)..=cp_clone.latest_clock_value {
-                let r_cache = cp_clone.unwrap_cache().read().unwrap();
-                r_cache.index.get(&t).and_then(|keys| {
-                    for key in keys.iter() {
-                        r_cache.data.get(key).and_then(|task| {
-                            handles.push(cp_clone.execute_task(*key, task.clone()));
-                            Some(())
-                        });
-                    }
-                    Some(())
-                });
-            }
-
-            // Join threads
-            if !handles.is_empty() {
-                for h in handles {
-                    h.join().unwrap();
-                }
-            }
-        });
-    }
-
-    fn execute_task(&self, key: Pubkey, task: Task) -> JoinHandle<()> {
-        let self_clone = self.clone();
-        let cp_arc: Arc<CronosPlugin> = Arc::new(self_clone);
-        let cp_clone = cp_arc.clone();
-
-        thread::spawn(move || {
-            // Lock the mutex for this task
-            let mutex = cp_clone
-                .unwrap_bucket()
-                .lock()
-                .unwrap()
-                .get_mutex((key, task.exec_at.unwrap()));
-            let guard = mutex.try_lock();
-            if guard.is_err() {
-                return;
-            };
-            let guard = guard.unwrap();
-
-            // Get accounts
-            let config = cronos_sdk::scheduler::state::Config::pda().0;
-            let fee = Fee::pda(task.daemon).0;
-
-            // Add accounts to exec instruction
-            let mut ix_exec = cronos_sdk::scheduler::instruction::task_exec(
-                cp_clone.unwrap_client().payer_pubkey(),
-                config,
-                task.daemon,
-                fee,
-                key,
-            );
-
-            // create account cache to dedupe accounts in exec ix
-            let mut acc_cache = HashMap::<Pubkey, AccountMetaData>::new();
-
-            for ix in &task.ixs {
-                for acc in &ix.accounts {
-                    match acc.is_writable {
-                        true => {
-                            if !acc_cache.contains_key(&acc.pubkey) {
-                                acc_cache.insert(acc.pubkey, acc.clone());
-                                ix_exec.accounts.push(AccountMeta::new(acc.pubkey, false))
-                            }
-                        }
-                        false => {
-                            if !acc_cache.contains_key(&acc.pubkey) {
-                                acc_cache.insert(acc.pubkey, acc.clone());
-                                ix_exec
-                                    .accounts
-                                    .push(AccountMeta::new_readonly(acc.pubkey, false))
-                            }
-                        }
-                    }
-                }
-            }
-
-            // add Program ID
-            ix_exec.accounts.push(AccountMeta::new_readonly(
-                task.ixs.first().unwrap().program_id,
-                false,
-            ));
-
-            // Sign and submit
-            let res = cp_clone.unwrap_client().sign_and_submit(
-                &[ix_exec],
-                format!("🤖 Executing task: {} {}", key, task.exec_at.unwrap()).as_str(),
-            );
-
-            // If exec failed, replicate the task data
-            if res.is_err() {
-                let err = res.err().unwrap();
-                info!("❌ {}", err);
-                let data = cp_clone.unwrap_client().get_account_data(&key).unwrap();
-                let task = Task::try_from(data).unwrap();
-                let mut w_cache = cp_clone.unwrap_cache().write().unwrap();
-                w_cache.insert(key, task);
-            }
-
-            // Drop the mutex
-            drop(guard)
-        })
-    }
 }
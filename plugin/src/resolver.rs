@@ -0,0 +1,142 @@
+use {
+    cronos_sdk::scheduler::state::InstructionData, solana_program::pubkey::Pubkey,
+    solana_sdk::instruction::AccountMeta, std::collections::HashMap,
+};
+
+/// Resolve the remaining accounts a task_exec instruction needs to invoke a task's instructions.
+///
+/// Accounts are merged across every instruction in first-seen order, keeping the strictest
+/// writable flag. The program id of every instruction is included. No account is marked as a
+/// signer: the daemon signs its instructions via PDA, and tasks can't require other signers.
+pub fn resolve_accounts(daemon: &Pubkey, ixs: &[InstructionData]) -> Vec<AccountMeta> {
+    let mut metas: Vec<AccountMeta> = vec![];
+    let mut positions: HashMap<Pubkey, usize> = HashMap::new();
+
+    let mut merge = |pubkey: Pubkey, is_writable: bool| match positions.get(&pubkey) {
+        Some(pos) => metas[*pos].is_writable |= is_writable,
+        None => {
+            positions.insert(pubkey, metas.len());
+            metas.push(AccountMeta {
+                pubkey,
+                is_signer: false,
+                is_writable,
+            });
+        }
+    };
+
+    for ix in ixs {
+        for acc in &ix.accounts {
+            // The daemon is mutated by task_exec, so it is always writable
+            merge(acc.pubkey, acc.is_writable || acc.pubkey == *daemon);
+        }
+    }
+    for ix in ixs {
+        merge(ix.program_id, false);
+    }
+
+    metas
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, cronos_sdk::scheduler::state::AccountMetaData};
+
+    fn meta(pubkey: Pubkey, is_signer: bool, is_writable: bool) -> AccountMetaData {
+        AccountMetaData {
+            pubkey,
+            is_signer,
+            is_writable,
+        }
+    }
+
+    #[test]
+    fn test_resolve_multi_program_task() {
+        let daemon = Pubkey::new_unique();
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+        let shared = Pubkey::new_unique();
+        let only_a = Pubkey::new_unique();
+        let only_b = Pubkey::new_unique();
+
+        let ixs = vec![
+            InstructionData {
+                program_id: program_a,
+                accounts: vec![
+                    meta(daemon, true, false),
+                    meta(shared, false, false),
+                    meta(only_a, false, true),
+                ],
+                data: vec![],
+            },
+            InstructionData {
+                program_id: program_b,
+                accounts: vec![meta(shared, false, true), meta(only_b, false, false)],
+                data: vec![],
+            },
+        ];
+
+        let metas = resolve_accounts(&daemon, &ixs);
+        assert_eq!(
+            metas,
+            vec![
+                AccountMeta::new(daemon, false),
+                AccountMeta::new(shared, false),
+                AccountMeta::new(only_a, false),
+                AccountMeta::new_readonly(only_b, false),
+                AccountMeta::new_readonly(program_a, false),
+                AccountMeta::new_readonly(program_b, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_dedupes_program_ids() {
+        let daemon = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+
+        let ix = InstructionData {
+            program_id: program,
+            accounts: vec![meta(account, false, true)],
+            data: vec![],
+        };
+        let metas = resolve_accounts(&daemon, &[ix.clone(), ix]);
+        assert_eq!(
+            metas,
+            vec![
+                AccountMeta::new(account, false),
+                AccountMeta::new_readonly(program, false),
+            ]
+        );
+        assert!(metas.iter().all(|m| !m.is_signer));
+    }
+
+    #[test]
+    fn test_resolve_program_passed_as_account() {
+        let daemon = Pubkey::new_unique();
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+
+        // Program A invokes program B, so B is also one of its accounts
+        let ixs = vec![
+            InstructionData {
+                program_id: program_a,
+                accounts: vec![meta(program_b, false, false)],
+                data: vec![],
+            },
+            InstructionData {
+                program_id: program_b,
+                accounts: vec![],
+                data: vec![],
+            },
+        ];
+        let metas = resolve_accounts(&daemon, &ixs);
+        assert_eq!(
+            metas,
+            vec![
+                AccountMeta::new_readonly(program_b, false),
+                AccountMeta::new_readonly(program_a, false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use {
+    crate::{client::AsyncClient, Config, Filter},
+    log::{info, warn},
+    solana_geyser_plugin_interface::geyser_plugin_interface::{
+        GeyserPluginError, Result as PluginResult,
+    },
+    std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::{Arc, RwLock, Weak},
+        thread,
+        time::{Duration, SystemTime},
+    },
+    tokio::sync::Semaphore,
+};
+
+/// How often to check the config file for changes.
+pub const RELOAD_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The plugin state derived from the config file, swapped as a whole when the file changes.
+pub struct Settings {
+    pub client: Arc<AsyncClient>,
+    pub config: Arc<Config>,
+    pub filter: Filter,
+    /// Permits for the workers simulating and executing tasks, shared across ticks.
+    pub workers: Arc<Semaphore>,
+}
+
+impl Settings {
+    /// Read the config file and build the state it describes.
+    pub fn load<P: AsRef<Path>>(config_path: P) -> PluginResult<Self> {
+        let config = Config::read_from(config_path)?;
+        let client = AsyncClient::new(
+            &config.keypath,
+            &config.keypaths,
+            config.min_fee_payer_balance(),
+            config.rpc_url.clone(),
+            config.commitment(),
+        )
+        .map_err(|e| GeyserPluginError::ConfigFileReadError {
+            msg: format!(
+                "Failed to create an RPC client for {}: {}",
+                config.rpc_url, e
+            ),
+        })?;
+        Ok(Self {
+            client: Arc::new(client),
+            filter: Filter::new(&config),
+            workers: Arc::new(Semaphore::new(config.worker_count())),
+            config: Arc::new(config),
+        })
+    }
+}
+
+/// Watch the config file and swap in new settings whenever it changes.
+///
+/// Invalid configs are logged and ignored, leaving the running settings in place.
+/// The watcher stops once the plugin drops its settings on unload.
+pub fn watch(config_path: &str, settings: &Arc<RwLock<Arc<Settings>>>) {
+    let config_path = PathBuf::from(config_path);
+    let settings: Weak<RwLock<Arc<Settings>>> = Arc::downgrade(settings);
+    let mut last_modified = modified(&config_path);
+
+    thread::spawn(move || loop {
+        thread::sleep(RELOAD_INTERVAL);
+        let settings = match settings.upgrade() {
+            Some(settings) => settings,
+            None => return,
+        };
+        reload(&config_path, &settings, &mut last_modified);
+    });
+}
+
+/// Swap in new settings if the config file was modified since `last_modified`.
+fn reload(
+    config_path: &Path,
+    settings: &RwLock<Arc<Settings>>,
+    last_modified: &mut Option<SystemTime>,
+) {
+    let modified = modified(config_path);
+    if modified == *last_modified {
+        return;
+    }
+    *last_modified = modified;
+
+    match Settings::load(config_path) {
+        Ok(new_settings) => {
+            info!("Reloaded config from {:?}", config_path);
+            let mut settings = settings.write().unwrap();
+            new_settings
+                .client
+                .fee_payers
+                .keep_balances(&settings.client.fee_payers);
+            *settings = Arc::new(new_settings);
+        }
+        Err(err) => warn!("Rejected config from {:?}: {}", config_path, err),
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::signature::{write_keypair_file, Keypair},
+        std::{env, fs::File},
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("cronos-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn touch(path: &Path, modified: SystemTime) {
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    fn write_config(dir: &Path, keypath: &Path, rpc_url: &str) -> PathBuf {
+        let path = dir.join("config.json");
+        fs::write(
+            &path,
+            format!(
+                r#"{{"keypath": {:?}, "rpc_url": {:?}, "program_includes": []}}"#,
+                keypath, rpc_url
+            ),
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_configs() {
+        let dir = temp_dir("settings");
+        let keypath = dir.join("id.json");
+        write_keypair_file(&Keypair::new(), &keypath).unwrap();
+
+        let path = write_config(&dir, &keypath, "http://127.0.0.1:8899");
+        let settings = Settings::load(&path).unwrap();
+        assert_eq!(settings.config.rpc_url, "http://127.0.0.1:8899");
+
+        // Missing keypair
+        let path = write_config(&dir, &dir.join("missing.json"), "http://127.0.0.1:8899");
+        assert!(Settings::load(&path).is_err());
+
+        // Malformed json
+        fs::write(&path, "{").unwrap();
+        assert!(Settings::load(&path).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reload_swaps_valid_configs() {
+        let dir = temp_dir("reload");
+        let keypath = dir.join("id.json");
+        write_keypair_file(&Keypair::new(), &keypath).unwrap();
+        let path = write_config(&dir, &keypath, "http://127.0.0.1:8899");
+        let settings = RwLock::new(Arc::new(Settings::load(&path).unwrap()));
+        let mut last_modified = modified(&path);
+        let start = last_modified.unwrap();
+
+        // An unchanged file keeps the running settings
+        let original = settings.read().unwrap().clone();
+        reload(&path, &settings, &mut last_modified);
+        assert!(Arc::ptr_eq(&original, &settings.read().unwrap()));
+
+        // A valid config swaps them
+        write_config(&dir, &keypath, "http://127.0.0.1:9999");
+        touch(&path, start + Duration::from_secs(10));
+        reload(&path, &settings, &mut last_modified);
+        let reloaded = settings.read().unwrap().clone();
+        assert!(!Arc::ptr_eq(&original, &reloaded));
+        assert_eq!(reloaded.config.rpc_url, "http://127.0.0.1:9999");
+
+        // An invalid config keeps the last valid settings
+        fs::write(&path, "{").unwrap();
+        touch(&path, start + Duration::from_secs(20));
+        reload(&path, &settings, &mut last_modified);
+        assert!(Arc::ptr_eq(&reloaded, &settings.read().unwrap()));
+        assert_eq!(last_modified, Some(start + Duration::from_secs(20)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
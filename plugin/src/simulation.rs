@@ -0,0 +1,173 @@
+use {
+    solana_program::pubkey::Pubkey,
+    solana_sdk::{compute_budget, transaction::TransactionError},
+};
+
+/// What a task_exec simulation says about the transaction's chances of landing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimulationOutcome {
+    /// The transaction is expected to succeed.
+    Success { units_consumed: Option<u64> },
+    /// The task is not due yet according to the cluster clock, or not yet executable by this node.
+    NotDue,
+    /// The task's instructions will fail, so submitting is a waste of fees.
+    Failed { reason: String },
+    /// The simulation failed for a reason unrelated to the task, such as an expired blockhash.
+    Inconclusive { reason: String },
+}
+
+/// Classify a task_exec simulation from its error and program logs.
+pub fn parse(
+    err: Option<&TransactionError>,
+    logs: &[String],
+    units_consumed: Option<u64>,
+    scheduler_program_id: &Pubkey,
+) -> SimulationOutcome {
+    let err = match err {
+        None => return SimulationOutcome::Success { units_consumed },
+        Some(err) => err,
+    };
+
+    // Scheduler errors are logged by anchor with their error code name
+    if logs.iter().any(|l| {
+        l.contains("Error Code: TaskNotDue") || l.contains("Error Code: NotAuthorizedDelegate")
+    }) {
+        return SimulationOutcome::NotDue;
+    }
+    if logs.iter().any(|l| l.contains("Error Code: TaskFailed")) {
+        return SimulationOutcome::Failed {
+            reason: "task instruction invocation failed".to_string(),
+        };
+    }
+    if logs.iter().any(|l| l.contains("Error Code: PoolInvalid")) {
+        return SimulationOutcome::Failed {
+            reason: "the network pool account is invalid".to_string(),
+        };
+    }
+
+    // Failures of any program invoked by the task
+    let scheduler_program_id = scheduler_program_id.to_string();
+    let compute_budget_program_id = compute_budget::id().to_string();
+    for log in logs {
+        if let Some((program_id, reason)) = parse_program_failure(log) {
+            if program_id != scheduler_program_id && program_id != compute_budget_program_id {
+                return SimulationOutcome::Failed {
+                    reason: format!("program {} failed: {}", program_id, reason),
+                };
+            }
+        }
+    }
+
+    SimulationOutcome::Inconclusive {
+        reason: err.to_string(),
+    }
+}
+
+/// Parse a "Program <id> failed: <reason>" log line.
+fn parse_program_failure(log: &str) -> Option<(&str, &str)> {
+    let rest = log.strip_prefix("Program ")?;
+    let (program_id, reason) = rest.split_once(" failed: ")?;
+    Some((program_id, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::instruction::InstructionError};
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_success() {
+        let scheduler = Pubkey::new_unique();
+        assert_eq!(
+            parse(None, &[], Some(1234), &scheduler),
+            SimulationOutcome::Success {
+                units_consumed: Some(1234)
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_task_errors() {
+        let scheduler = Pubkey::new_unique();
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(6011));
+
+        let outcome = parse(
+            Some(&err),
+            &logs(&[
+                "Program log: AnchorError occurred. Error Code: TaskNotDue. Error Number: 6010.",
+            ]),
+            None,
+            &scheduler,
+        );
+        assert_eq!(outcome, SimulationOutcome::NotDue);
+
+        let outcome = parse(
+            Some(&err),
+            &logs(&[
+                "Program log: AnchorError occurred. Error Code: NotAuthorizedDelegate. Error Number: 6013.",
+            ]),
+            None,
+            &scheduler,
+        );
+        assert_eq!(outcome, SimulationOutcome::NotDue);
+
+        let outcome = parse(
+            Some(&err),
+            &logs(&[
+                "Program log: AnchorError occurred. Error Code: TaskFailed. Error Number: 6011.",
+            ]),
+            None,
+            &scheduler,
+        );
+        assert!(matches!(outcome, SimulationOutcome::Failed { .. }));
+
+        let outcome = parse(
+            Some(&err),
+            &logs(&[
+                "Program log: AnchorError occurred. Error Code: PoolInvalid. Error Number: 6015.",
+            ]),
+            None,
+            &scheduler,
+        );
+        assert!(matches!(outcome, SimulationOutcome::Failed { .. }));
+    }
+
+    #[test]
+    fn test_parse_inner_program_failure() {
+        let scheduler = Pubkey::new_unique();
+        let inner = Pubkey::new_unique();
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(1));
+
+        let outcome = parse(
+            Some(&err),
+            &logs(&[
+                &format!("Program {} invoke [2]", inner),
+                &format!("Program {} failed: custom program error: 0x1", inner),
+                &format!("Program {} failed: custom program error: 0x1", scheduler),
+            ]),
+            None,
+            &scheduler,
+        );
+        assert_eq!(
+            outcome,
+            SimulationOutcome::Failed {
+                reason: format!("program {} failed: custom program error: 0x1", inner)
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_inconclusive() {
+        let scheduler = Pubkey::new_unique();
+        let outcome = parse(
+            Some(&TransactionError::BlockhashNotFound),
+            &[],
+            None,
+            &scheduler,
+        );
+        assert!(matches!(outcome, SimulationOutcome::Inconclusive { .. }));
+    }
+}
@@ -23,6 +23,9 @@ pub enum CronosError {
     #[msg("The snapshot has already been captured and is not currently in progress")]
     SnapshotNotInProgress,
 
+    #[msg("The snapshot is not archived")]
+    SnapshotNotArchived,
+
     #[msg("The snapshot is not current")]
     SnapshotNotCurrent,
 
@@ -31,6 +34,84 @@ pub enum CronosError {
 
     #[msg("A new snapshot page is not needed")]
     SnapshotPageNotNeeded,
+
+    #[msg("The snapshot has no stake to sample nodes from")]
+    SnapshotNoStake,
+
+    #[msg("The snapshot whose claim window closes must be provided")]
+    SnapshotExpiringInvalid,
+
+    #[msg("The pool has no draw committed in an earlier slot")]
+    PoolCommitMissing,
+
+    #[msg("The hash of the pool's committed slot is no longer available, so the draw must be committed again")]
+    PoolCommitExpired,
+
+    #[msg("The pool size must be greater than zero")]
+    PoolSizeInvalid,
+
+    #[msg("The pool no longer holds the delegates from when the task came due")]
+    PoolHistoryMissing,
+
+    #[msg("The pool has no delegates to report a miss against")]
+    PoolEmpty,
+
+    #[msg("The slash rate may not exceed 100%")]
+    SlashRateInvalid,
+
+    #[msg("The slash threshold must be greater than zero")]
+    SlashThresholdInvalid,
+
+    #[msg("The heartbeat window may not be negative")]
+    HeartbeatWindowInvalid,
+
+    #[msg("The miss window may not be negative")]
+    MissWindowInvalid,
+
+    #[msg("A miss has already been reported for this node at or after this time")]
+    MissAlreadyReported,
+
+    #[msg("The task's execution window has not elapsed yet")]
+    MissWindowOpen,
+
+    #[msg("A miss must be reported ahead of the task's execution in the same transaction")]
+    MissUnproven,
+
+    #[msg("The node is not a pool delegate")]
+    NodeNotDelegate,
+
+    #[msg("The node metadata is too long")]
+    NodeMetadataTooLong,
+
+    #[msg("The node was not captured by the snapshot")]
+    NodeNotInSnapshot,
+
+    #[msg("The node must unstake and withdraw all of its tokens first")]
+    NodeHasStake,
+
+    #[msg("The node has already claimed the rewards of this snapshot, or its claim window has closed")]
+    RewardsAlreadyClaimed,
+
+    #[msg("The node must claim the rewards of its earlier snapshots first")]
+    RewardsClaimedOutOfOrder,
+
+    #[msg("The node does not have enough stake")]
+    StakeInsufficient,
+
+    #[msg("The scheduler task is not due")]
+    TaskInvalid,
+
+    #[msg("Only the network's own tasks oblige the delegates to execute them")]
+    TaskNotObliged,
+
+    #[msg("There are no unbonding tokens to withdraw")]
+    UnbondingEmpty,
+
+    #[msg("The unbonding period has not elapsed yet")]
+    UnbondingInProgress,
+
+    #[msg("The unbonding period may not be negative")]
+    UnbondingPeriodInvalid,
 }
 
 #[error_code]
@@ -43,4 +124,7 @@ pub enum SnapshotError {
 pub enum PoolError {
     #[msg("The provided snapshot page doesn't hold the sampled node")]
     InvalidSnapshotPage,
+
+    #[msg("The provided node isn't the sampled node")]
+    InvalidNode,
 }
@@ -1,5 +1,5 @@
 use {
-    crate::state::*,
+    crate::state::{Rewards, *},
     anchor_lang::{
         prelude::*, 
         solana_program::{system_program, sysvar}
@@ -14,6 +14,7 @@ use {
     pool_bump: u8,
     registry_bump: u8,
     registry_page_bump: u8,
+    rewards_bump: u8,
     snapshot_bump: u8,
     snapshot_page_bump: u8
 )]
@@ -44,7 +45,7 @@ pub struct Initialize<'info> {
 
     #[account(
         init,
-        seeds = [SEED_REGISTRY],
+        seeds = [SEED_POOL],
         bump,
         payer = admin,
         space = 8 + size_of::<Pool>(),
@@ -72,6 +73,15 @@ pub struct Initialize<'info> {
     )]
     pub registry_page: Account<'info, RegistryPage>,
 
+    #[account(
+        init,
+        seeds = [SEED_REWARDS],
+        bump,
+        payer = admin,
+        space = 8 + size_of::<Rewards>(),
+    )]
+    pub rewards: Account<'info, Rewards>,
+
     #[account(
         init,
         seeds = [
@@ -113,8 +123,10 @@ pub fn handler(
     pool_bump: u8,
     registry_bump: u8,
     registry_page_bump: u8,
+    rewards_bump: u8,
     snapshot_bump: u8,
-    snapshot_page_bump: u8
+    snapshot_page_bump: u8,
+    crank_authority: Pubkey,
 ) -> Result<()> {
     let admin = &ctx.accounts.admin;
     let clock = &ctx.accounts.clock;
@@ -123,13 +135,34 @@ pub fn handler(
     let pool = &mut ctx.accounts.pool;
     let registry = &mut ctx.accounts.registry;
     let registry_page = &mut ctx.accounts.registry_page;
+    let rewards = &mut ctx.accounts.rewards;
     let snapshot = &mut ctx.accounts.snapshot;
     let snapshot_page = &mut ctx.accounts.snapshot_page;
 
-    config.new(admin.key(), config_bump)?;
+    // Bootstrapping the network:
+    //
+    // 1. Initialize the scheduler, which creates the daemon of the scheduler's authority.
+    // 2. Initialize the network with that daemon as the crank authority.
+    // 3. Install an admin task on the scheduler which signs snapshot_start as the daemon
+    //    on a recurring schedule. This locks the registry to start a new snapshot.
+    // 4. While the registry is locked, nodes crank the rest of the snapshot with the
+    //    permissionless snapshot_new, snapshot_page_new, snapshot_capture and
+    //    snapshot_rotate instructions. Rotating the snapshot unlocks the registry.
+    // 5. Install a second admin task which signs pool_commit as the daemon every minute,
+    //    committing the pool's next draw. Nodes draw it with the permissionless pool_cycle in
+    //    a later slot, from the hash of the committed slot, sampling a delegate from the
+    //    current snapshot in proportion to its captured stake.
+    // 6. Fees which the scheduler distributes to the rewards vault are allocated to the
+    //    current snapshot as it's archived, and nodes claim their share with node_claim_rewards.
+    //    Whatever is unclaimed once a snapshot leaves the claim window is allocated again.
+    //
+    // The initial snapshot of the empty registry is taken and rotated in here.
+    config.new(admin.key(), config_bump, crank_authority)?;
     pool.new(pool_bump)?;
     registry.new(registry_bump, mint)?;
     registry.new_page(registry_page, registry_page_bump)?;
+    rewards.new(rewards_bump)?;
+    registry.lock()?;
     registry.new_snapshot(snapshot, snapshot_bump)?;
     snapshot.new_page(snapshot_page, snapshot_page_bump, registry)?;
     registry.rotate_snapshot(clock, None, snapshot)?;
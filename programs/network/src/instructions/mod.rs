@@ -1,3 +1,39 @@
+pub mod admin_config_update;
 pub mod initialize;
+pub mod node_claim_rewards;
+pub mod node_deregister;
+pub mod node_heartbeat;
+pub mod node_register;
+pub mod node_stake;
+pub mod node_unstake;
+pub mod node_update;
+pub mod node_withdraw;
+pub mod pool_commit;
+pub mod pool_cycle;
+pub mod registry_page_new;
+pub mod report_miss;
+pub mod snapshot_capture;
+pub mod snapshot_new;
+pub mod snapshot_page_new;
+pub mod snapshot_rotate;
+pub mod snapshot_start;
 
-pub use initialize::*;
\ No newline at end of file
+pub use admin_config_update::*;
+pub use initialize::*;
+pub use node_claim_rewards::*;
+pub use node_deregister::*;
+pub use node_heartbeat::*;
+pub use node_register::*;
+pub use node_stake::*;
+pub use node_unstake::*;
+pub use node_update::*;
+pub use node_withdraw::*;
+pub use pool_commit::*;
+pub use pool_cycle::*;
+pub use registry_page_new::*;
+pub use report_miss::*;
+pub use snapshot_capture::*;
+pub use snapshot_new::*;
+pub use snapshot_page_new::*;
+pub use snapshot_rotate::*;
+pub use snapshot_start::*;
@@ -0,0 +1,70 @@
+use {
+    crate::{errors::CronosError, state::{Rewards, *}},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(entry_id: u64)]
+pub struct NodeClaimRewards<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE,
+            authority.key().as_ref()
+        ],
+        bump = node.bump,
+        has_one = authority,
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(
+        seeds = [SEED_REGISTRY],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [SEED_REWARDS],
+        bump = rewards.bump,
+    )]
+    pub rewards: Account<'info, Rewards>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SNAPSHOT,
+            snapshot.id.to_be_bytes().as_ref()
+        ],
+        bump = snapshot.bump,
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        seeds = [
+            SEED_SNAPSHOT_PAGE,
+            snapshot.key().as_ref(),
+            SnapshotPage::page_id(entry_id).to_be_bytes().as_ref()
+        ],
+        bump = snapshot_page.bump,
+    )]
+    pub snapshot_page: Account<'info, SnapshotPage>,
+}
+
+pub fn handler(ctx: Context<NodeClaimRewards>, entry_id: u64) -> Result<()> {
+    let authority = &mut ctx.accounts.authority;
+    let node = &mut ctx.accounts.node;
+    let registry = &ctx.accounts.registry;
+    let rewards = &mut ctx.accounts.rewards;
+    let snapshot = &mut ctx.accounts.snapshot;
+    let snapshot_page = &ctx.accounts.snapshot_page;
+
+    // Only the page holding the node's entry is loaded, however large the snapshot
+    let entry = snapshot_page
+        .entry(entry_id)
+        .ok_or(CronosError::NodeNotInSnapshot)?;
+    rewards.claim(authority, node, registry, snapshot, entry)
+}
@@ -0,0 +1,111 @@
+use {
+    crate::{errors::CronosError, state::*},
+    anchor_lang::{prelude::*, AccountsExit},
+    anchor_spl::token::{Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+#[instruction(registry_page_id: u64)]
+pub struct NodeDeregister<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = authority_tokens.owner == authority.key(),
+        constraint = authority_tokens.mint == tokens.mint,
+    )]
+    pub authority_tokens: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE,
+            authority.key().as_ref()
+        ],
+        bump = node.bump,
+        has_one = authority,
+        close = authority,
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(
+        mut,
+        seeds = [SEED_REGISTRY],
+        bump = registry.bump,
+        constraint = !registry.is_locked @ CronosError::RegistryLocked,
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_REGISTRY_PAGE,
+            registry_page_id.to_be_bytes().as_ref()
+        ],
+        bump = registry_page.bump,
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE_TOKENS,
+            node.key().as_ref()
+        ],
+        bump,
+    )]
+    pub tokens: Account<'info, TokenAccount>,
+
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, NodeDeregister<'info>>,
+    _registry_page_id: u64,
+) -> Result<()> {
+    let authority = &ctx.accounts.authority;
+    let authority_tokens = &ctx.accounts.authority_tokens;
+    let node = &ctx.accounts.node;
+    let registry = &mut ctx.accounts.registry;
+    let registry_page = &mut ctx.accounts.registry_page;
+    let tokens = &ctx.accounts.tokens;
+    let token_program = &ctx.accounts.token_program;
+
+    // If the node isn't on the last page, the last page is passed in the remaining accounts
+    let mut last_registry_page = match ctx.remaining_accounts.first() {
+        Some(account_info) => {
+            let last_registry_page = Account::<RegistryPage>::try_from(account_info)?;
+            require!(
+                last_registry_page.key()
+                    == RegistryPage::pda(registry.page_count.checked_sub(1).unwrap()).0,
+                CronosError::PageRangeInvalid
+            );
+            Some(last_registry_page)
+        }
+        None => None,
+    };
+
+    // Swap the registry's last node into the node's slot
+    registry.remove_node(node, registry_page, last_registry_page.as_mut())?;
+
+    // Remove the last page if it was emptied, so every page but the last stays full
+    match last_registry_page {
+        Some(last_registry_page) => {
+            if last_registry_page.nodes.is_empty() {
+                registry.remove_page(&last_registry_page, authority.to_account_info())?;
+            } else {
+                last_registry_page.exit(&crate::ID)?;
+            }
+        }
+        None => {
+            if registry_page.nodes.is_empty() && registry.page_count > 1 {
+                registry.remove_page(registry_page, authority.to_account_info())?;
+            }
+        }
+    }
+
+    // Return the node's vault and account rent to the authority
+    node.close_tokens(authority, authority_tokens, tokens, token_program)
+}
@@ -0,0 +1,30 @@
+use {
+    crate::state::*,
+    anchor_lang::{prelude::*, solana_program::sysvar},
+};
+
+#[derive(Accounts)]
+pub struct NodeHeartbeat<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(address = sysvar::clock::ID)]
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE,
+            authority.key().as_ref()
+        ],
+        bump = node.bump,
+        has_one = authority,
+    )]
+    pub node: Account<'info, Node>,
+}
+
+pub fn handler(ctx: Context<NodeHeartbeat>) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let node = &mut ctx.accounts.node;
+
+    node.heartbeat(clock)
+}
@@ -0,0 +1,85 @@
+use {
+    crate::{state::*, utils::realloc},
+    anchor_lang::{prelude::*, solana_program::{system_program, sysvar}},
+    anchor_spl::token::{Mint, Token, TokenAccount},
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+#[instruction(node_bump: u8)]
+pub struct NodeRegister<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(address = registry.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        seeds = [
+            SEED_NODE,
+            authority.key().as_ref()
+        ],
+        bump,
+        payer = authority,
+        space = 8 + size_of::<Node>(),
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(
+        mut, 
+        seeds = [SEED_REGISTRY], 
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_REGISTRY_PAGE,
+            registry.page_count.checked_sub(1).unwrap().to_be_bytes().as_ref()
+        ],
+        bump = registry_page.bump,
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    #[account(
+        init,
+        seeds = [
+            SEED_NODE_TOKENS,
+            node.key().as_ref()
+        ],
+        bump,
+        payer = authority,
+        token::mint = mint,
+        token::authority = node,
+    )]
+    pub tokens: Account<'info, TokenAccount>,
+
+    #[account(address = sysvar::rent::ID)]
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<NodeRegister>, node_bump: u8) -> Result<()> {
+    let authority = &mut ctx.accounts.authority;
+    let node = &mut ctx.accounts.node;
+    let registry = &mut ctx.accounts.registry;
+    let registry_page = &mut ctx.accounts.registry_page;
+    let system_program = &ctx.accounts.system_program;
+
+    // Grow the registry page to fit one more node
+    realloc(
+        registry_page.to_account_info(),
+        8 + registry_page.try_to_vec()?.len() + size_of::<Pubkey>(),
+        authority.to_account_info(),
+        system_program.to_account_info(),
+    )?;
+
+    registry.new_node(authority, node, node_bump, registry_page)
+}
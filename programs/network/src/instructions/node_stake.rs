@@ -0,0 +1,59 @@
+use {
+    crate::{errors::CronosError, state::*},
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct NodeStake<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = authority_tokens.owner == authority.key(),
+        constraint = authority_tokens.mint == registry.mint,
+    )]
+    pub authority_tokens: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE,
+            authority.key().as_ref()
+        ],
+        bump = node.bump,
+        has_one = authority,
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(
+        seeds = [SEED_REGISTRY],
+        bump = registry.bump,
+        constraint = !registry.is_locked @ CronosError::RegistryLocked,
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE_TOKENS,
+            node.key().as_ref()
+        ],
+        bump,
+    )]
+    pub tokens: Account<'info, TokenAccount>,
+
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<NodeStake>, amount: u64) -> Result<()> {
+    let authority = &ctx.accounts.authority;
+    let authority_tokens = &ctx.accounts.authority_tokens;
+    let node = &mut ctx.accounts.node;
+    let tokens = &ctx.accounts.tokens;
+    let token_program = &ctx.accounts.token_program;
+
+    node.stake(amount, authority, authority_tokens, tokens, token_program)
+}
@@ -0,0 +1,45 @@
+use {
+    crate::{errors::CronosError, state::*},
+    anchor_lang::{prelude::*, solana_program::sysvar},
+};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct NodeUnstake<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(address = sysvar::clock::ID)]
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        seeds = [SEED_CONFIG],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE,
+            authority.key().as_ref()
+        ],
+        bump = node.bump,
+        has_one = authority,
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(
+        seeds = [SEED_REGISTRY],
+        bump = registry.bump,
+        constraint = !registry.is_locked @ CronosError::RegistryLocked,
+    )]
+    pub registry: Account<'info, Registry>,
+}
+
+pub fn handler(ctx: Context<NodeUnstake>, amount: u64) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let config = &ctx.accounts.config;
+    let node = &mut ctx.accounts.node;
+
+    node.unstake(amount, clock, config)
+}
@@ -0,0 +1,41 @@
+use {
+    crate::{state::*, utils::realloc},
+    anchor_lang::{prelude::*, solana_program::system_program},
+};
+
+#[derive(Accounts)]
+#[instruction(metadata: NodeMetadata)]
+pub struct NodeUpdate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE,
+            authority.key().as_ref()
+        ],
+        bump = node.bump,
+        has_one = authority,
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<NodeUpdate>, metadata: NodeMetadata) -> Result<()> {
+    let authority = &ctx.accounts.authority;
+    let node = &mut ctx.accounts.node;
+    let system_program = &ctx.accounts.system_program;
+
+    node.update(metadata)?;
+
+    // Grow the node to fit the new metadata
+    realloc(
+        node.to_account_info(),
+        8 + node.try_to_vec()?.len(),
+        authority.to_account_info(),
+        system_program.to_account_info(),
+    )
+}
@@ -0,0 +1,54 @@
+use {
+    crate::state::*,
+    anchor_lang::{prelude::*, solana_program::sysvar},
+    anchor_spl::token::{Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct NodeWithdraw<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = authority_tokens.owner == authority.key(),
+        constraint = authority_tokens.mint == tokens.mint,
+    )]
+    pub authority_tokens: Account<'info, TokenAccount>,
+
+    #[account(address = sysvar::clock::ID)]
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE,
+            authority.key().as_ref()
+        ],
+        bump = node.bump,
+        has_one = authority,
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_NODE_TOKENS,
+            node.key().as_ref()
+        ],
+        bump,
+    )]
+    pub tokens: Account<'info, TokenAccount>,
+
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<NodeWithdraw>) -> Result<()> {
+    let authority_tokens = &ctx.accounts.authority_tokens;
+    let clock = &ctx.accounts.clock;
+    let node = &mut ctx.accounts.node;
+    let tokens = &ctx.accounts.tokens;
+    let token_program = &ctx.accounts.token_program;
+
+    node.withdraw(clock, tokens, authority_tokens, token_program)
+}
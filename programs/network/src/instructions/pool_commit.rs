@@ -0,0 +1,34 @@
+use {
+    crate::state::*,
+    anchor_lang::{prelude::*, solana_program::sysvar},
+};
+
+#[derive(Accounts)]
+pub struct PoolCommit<'info> {
+    #[account(address = config.crank_authority)]
+    pub authority: Signer<'info>,
+
+    #[account(address = sysvar::clock::ID)]
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        seeds = [SEED_CONFIG],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<PoolCommit>) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let pool = &mut ctx.accounts.pool;
+
+    // Only the crank authority commits draws, on a schedule, so callers can't re-roll the pool
+    pool.commit(clock)
+}
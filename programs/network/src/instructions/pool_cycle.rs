@@ -0,0 +1,94 @@
+use {
+    crate::{state::*, utils::realloc},
+    anchor_lang::{prelude::*, solana_program::{system_program, sysvar}},
+};
+
+#[derive(Accounts)]
+#[instruction(entry_id: u64)]
+pub struct PoolCycle<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = sysvar::clock::ID)]
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        seeds = [SEED_CONFIG],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The sampled node may have deregistered since the snapshot, so its address is checked against the sampled entry when the pool cycles
+    pub node: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [SEED_REGISTRY], 
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    /// CHECK: The slot hashes sysvar is too large to deserialize, so the committed slot's hash is searched for in its data
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            SEED_SNAPSHOT,
+            registry.snapshot_count.checked_sub(1).unwrap().to_be_bytes().as_ref()
+        ],
+        bump = snapshot.bump,
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        seeds = [
+            SEED_SNAPSHOT_PAGE,
+            snapshot.key().as_ref(),
+            SnapshotPage::page_id(entry_id).to_be_bytes().as_ref()
+        ],
+        bump = snapshot_page.bump,
+    )]
+    pub snapshot_page: Account<'info, SnapshotPage>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<PoolCycle>, entry_id: u64) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let clock = &ctx.accounts.clock;
+    let config = &ctx.accounts.config;
+    let node = &ctx.accounts.node;
+    let pool = &mut ctx.accounts.pool;
+    let slot_hashes = &ctx.accounts.slot_hashes;
+    let snapshot = &ctx.accounts.snapshot;
+    let snapshot_page = &ctx.accounts.snapshot_page;
+    let system_program = &ctx.accounts.system_program;
+
+    // The sampled node's entry and its current account are passed, so its liveness is read
+    // from its latest heartbeat rather than from when the snapshot was captured
+    pool.cycle(
+        clock,
+        config,
+        &node.to_account_info(),
+        &slot_hashes.try_borrow_data()?,
+        snapshot,
+        snapshot_page,
+        entry_id,
+    )?;
+
+    // Grow the pool if a delegate was added
+    realloc(
+        pool.to_account_info(),
+        8 + pool.try_to_vec()?.len(),
+        payer.to_account_info(),
+        system_program.to_account_info(),
+    )
+}
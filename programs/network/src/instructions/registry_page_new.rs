@@ -0,0 +1,42 @@
+use {
+    crate::state::*,
+    anchor_lang::{prelude::*, solana_program::system_program},
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+#[instruction(registry_page_bump: u8)]
+pub struct RegistryPageNew<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut, 
+        seeds = [SEED_REGISTRY], 
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        seeds = [
+            SEED_REGISTRY_PAGE,
+            registry.page_count.to_be_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + size_of::<RegistryPage>(),
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegistryPageNew>, registry_page_bump: u8) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    let registry_page = &mut ctx.accounts.registry_page;
+
+    // The registry only accepts a new page once the last one is full
+    registry.new_page(registry_page, registry_page_bump)
+}
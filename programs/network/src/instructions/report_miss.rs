@@ -0,0 +1,142 @@
+use {
+    crate::{errors::CronosError, state::*},
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            instruction::Instruction,
+            sysvar::{
+                self,
+                instructions::{load_current_index_checked, load_instruction_at_checked},
+            },
+        },
+        AccountsExit, InstructionData,
+    },
+    anchor_spl::token::{Mint, Token, TokenAccount},
+    cronos_scheduler::state::Task,
+};
+
+#[derive(Accounts)]
+pub struct ReportMiss<'info> {
+    #[account(address = sysvar::clock::ID)]
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        seeds = [SEED_CONFIG],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The instructions sysvar is read with the sysvar's own helpers
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = registry.mint,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [SEED_POOL],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [SEED_REGISTRY],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [cronos_scheduler::state::SEED_CONFIG],
+        seeds::program = cronos_scheduler::ID,
+        bump = scheduler_config.bump,
+    )]
+    pub scheduler_config: Account<'info, cronos_scheduler::state::Config>,
+
+    pub task: Account<'info, Task>,
+
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ReportMiss<'info>>) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let config = &ctx.accounts.config;
+    let instructions = &ctx.accounts.instructions;
+    let mint = &ctx.accounts.mint;
+    let pool = &ctx.accounts.pool;
+    let scheduler_config = &ctx.accounts.scheduler_config;
+    let task = &ctx.accounts.task;
+    let token_program = &ctx.accounts.token_program;
+
+    // Only the network's own tasks are executed by every node, whatever tasks it chooses to
+    // serve, so only they oblige the delegates
+    require!(task.daemon == config.crank_authority, CronosError::TaskNotObliged);
+
+    // Verify the task is due and its execution window has elapsed. Only delegates may execute the
+    // task until the scheduler's grace period has elapsed, so the window lasts at least as long.
+    // A window which ends past the end of time never elapses.
+    let exec_at = task.exec_at.ok_or(CronosError::TaskInvalid)?;
+    let miss_window = config.miss_window.max(scheduler_config.delegate_grace_period);
+    let window_end = exec_at
+        .checked_add(miss_window)
+        .ok_or(CronosError::MissWindowOpen)?;
+    require!(clock.unix_timestamp >= window_end, CronosError::MissWindowOpen);
+
+    // The delegates which held their seats through the whole window are obliged to have executed
+    // the task, in proportion to their seats
+    let delegates = pool
+        .obliged_delegates(exec_at, window_end)
+        .ok_or(CronosError::PoolHistoryMissing)?;
+    require!(!delegates.is_empty(), CronosError::PoolEmpty);
+
+    // Verify the task is executed later in this transaction. The task still being due proves it
+    // wasn't executed in its window, and executing it proves it could have been.
+    let instructions = instructions.to_account_info();
+    let current_index = load_current_index_checked(&instructions)? as usize;
+    let is_proven = (current_index.checked_add(1).unwrap()..)
+        .map_while(|index| load_instruction_at_checked(index, &instructions).ok())
+        .any(|ix| is_task_exec(&ix, &task.key()));
+    require!(is_proven, CronosError::MissUnproven);
+
+    // Each obliged delegate's node and token vault are passed in the remaining accounts, in the
+    // order the delegates first appear in the pool at the start of the window
+    require!(
+        ctx.remaining_accounts.len() == delegates.len().checked_mul(2).unwrap(),
+        CronosError::NodeNotDelegate
+    );
+    let seat_count = delegates.iter().map(|(_, seats)| seats).sum::<u64>();
+    for ((delegate, seats), accounts) in delegates
+        .into_iter()
+        .zip(ctx.remaining_accounts.chunks(2))
+    {
+        // A delegate which has since deregistered has nothing left to slash
+        require!(
+            accounts[0].key() == Node::pda(delegate).0,
+            CronosError::NodeNotDelegate
+        );
+        if accounts[0].data_is_empty() {
+            continue;
+        }
+        let mut node = Account::<Node>::try_from(&accounts[0])?;
+        let tokens = Account::<TokenAccount>::try_from(&accounts[1])?;
+        require!(
+            tokens.key() == Node::tokens_pda(node.key()).0,
+            CronosError::NodeNotDelegate
+        );
+        node.miss(config, exec_at, seats, seat_count, mint, &tokens, token_program)?;
+        node.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+/// Whether the instruction is the scheduler's task_exec for the task. A task can only be passed
+/// to task_exec as the task being executed, so its position among the accounts isn't checked.
+fn is_task_exec(ix: &Instruction, task: &Pubkey) -> bool {
+    ix.program_id == cronos_scheduler::ID
+        && ix.data.get(..8) == Some(&cronos_scheduler::instruction::TaskExec {}.data()[..])
+        && ix.accounts.iter().any(|meta| meta.pubkey == *task)
+}
@@ -0,0 +1,75 @@
+use {
+    crate::{state::*, utils::realloc},
+    anchor_lang::{prelude::*, solana_program::system_program},
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+pub struct SnapshotCapture<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_REGISTRY], 
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [
+            SEED_REGISTRY_PAGE,
+            snapshot.page_count.checked_sub(1).unwrap().to_be_bytes().as_ref()
+        ],
+        bump = registry_page.bump,
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SNAPSHOT,
+            registry.snapshot_count.to_be_bytes().as_ref()
+        ],
+        bump = snapshot.bump,
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SNAPSHOT_PAGE,
+            snapshot.key().as_ref(),
+            snapshot.page_count.checked_sub(1).unwrap().to_be_bytes().as_ref()
+        ],
+        bump = snapshot_page.bump,
+    )]
+    pub snapshot_page: Account<'info, SnapshotPage>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SnapshotCapture<'info>>) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let registry_page = &ctx.accounts.registry_page;
+    let snapshot = &mut ctx.accounts.snapshot;
+    let snapshot_page = &mut ctx.accounts.snapshot_page;
+    let system_program = &ctx.accounts.system_program;
+
+    // The nodes to capture are passed in registry page order
+    let nodes = ctx
+        .remaining_accounts
+        .iter()
+        .map(Account::<Node>::try_from)
+        .collect::<Result<Vec<Account<Node>>>>()?;
+
+    // Grow the snapshot page to fit the new entries
+    realloc(
+        snapshot_page.to_account_info(),
+        8 + snapshot_page.try_to_vec()?.len() + nodes.len() * size_of::<SnapshotEntry>(),
+        payer.to_account_info(),
+        system_program.to_account_info(),
+    )?;
+
+    snapshot.capture(nodes.iter().collect(), registry_page, snapshot_page)
+}
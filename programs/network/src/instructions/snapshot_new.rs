@@ -0,0 +1,41 @@
+use {
+    crate::state::*,
+    anchor_lang::{prelude::*, solana_program::system_program},
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+#[instruction(snapshot_bump: u8)]
+pub struct SnapshotNew<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_REGISTRY], 
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        seeds = [
+            SEED_SNAPSHOT,
+            registry.snapshot_count.to_be_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + size_of::<Snapshot>(),
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SnapshotNew>, snapshot_bump: u8) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    let snapshot = &mut ctx.accounts.snapshot;
+
+    // Open the snapshot of the locked registry
+    registry.new_snapshot(snapshot, snapshot_bump)
+}
@@ -0,0 +1,52 @@
+use {
+    crate::state::*,
+    anchor_lang::{prelude::*, solana_program::system_program},
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+#[instruction(snapshot_page_bump: u8)]
+pub struct SnapshotPageNew<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_REGISTRY], 
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SNAPSHOT,
+            registry.snapshot_count.to_be_bytes().as_ref()
+        ],
+        bump = snapshot.bump,
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        init,
+        seeds = [
+            SEED_SNAPSHOT_PAGE,
+            snapshot.key().as_ref(),
+            snapshot.page_count.to_be_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + size_of::<SnapshotPage>(),
+    )]
+    pub snapshot_page: Account<'info, SnapshotPage>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SnapshotPageNew>, snapshot_page_bump: u8) -> Result<()> {
+    let registry = &ctx.accounts.registry;
+    let snapshot = &mut ctx.accounts.snapshot;
+    let snapshot_page = &mut ctx.accounts.snapshot_page;
+
+    snapshot.new_page(snapshot_page, snapshot_page_bump, registry)
+}
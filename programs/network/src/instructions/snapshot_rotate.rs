@@ -0,0 +1,74 @@
+use {
+    crate::{errors::CronosError, state::{Rewards, *}},
+    anchor_lang::{prelude::*, solana_program::sysvar, AccountsExit},
+};
+
+#[derive(Accounts)]
+pub struct SnapshotRotate<'info> {
+    #[account(address = sysvar::clock::ID)]
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SNAPSHOT,
+            registry.snapshot_count.checked_sub(1).unwrap().to_be_bytes().as_ref()
+        ],
+        bump = current_snapshot.bump,
+    )]
+    pub current_snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SNAPSHOT,
+            registry.snapshot_count.to_be_bytes().as_ref()
+        ],
+        bump = next_snapshot.bump,
+    )]
+    pub next_snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        mut, 
+        seeds = [SEED_REGISTRY], 
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [SEED_REWARDS],
+        bump = rewards.bump,
+    )]
+    pub rewards: Account<'info, Rewards>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SnapshotRotate<'info>>) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let current_snapshot = &mut ctx.accounts.current_snapshot;
+    let next_snapshot = &mut ctx.accounts.next_snapshot;
+    let registry = &mut ctx.accounts.registry;
+    let rewards = &mut ctx.accounts.rewards;
+
+    // Release what's unclaimed from the snapshot whose claim window closes, so it's allocated
+    // again below. Once there is such a snapshot, it's passed in the remaining accounts.
+    if let Some(expiring_snapshot_id) = expiring_snapshot_id(next_snapshot.id) {
+        let account_info = ctx
+            .remaining_accounts
+            .first()
+            .ok_or(CronosError::SnapshotExpiringInvalid)?;
+        let mut expiring_snapshot = Account::<Snapshot>::try_from(account_info)?;
+        require!(
+            expiring_snapshot.key() == Snapshot::pda(expiring_snapshot_id).0,
+            CronosError::SnapshotExpiringInvalid
+        );
+        rewards.release(&mut expiring_snapshot)?;
+        expiring_snapshot.exit(&crate::ID)?;
+    }
+
+    // Pay the fees collected while the current snapshot was in place to its stakers
+    rewards.allocate(current_snapshot)?;
+
+    // The registry is unlocked once the completed snapshot becomes current
+    registry.rotate_snapshot(clock, Some(current_snapshot), next_snapshot)
+}
@@ -0,0 +1,30 @@
+use {
+    crate::state::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SnapshotStart<'info> {
+    #[account(address = config.crank_authority)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_CONFIG],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut, 
+        seeds = [SEED_REGISTRY], 
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+}
+
+pub fn handler(ctx: Context<SnapshotStart>) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+
+    // Lock the registry until the next snapshot is captured and rotated in
+    registry.lock()
+}
@@ -1,11 +1,13 @@
 pub mod errors;
 pub mod pda;
 pub mod state;
+pub mod utils;
 
 mod instructions;
 
 use anchor_lang::prelude::*;
 use instructions::*;
+use state::*;
 
 declare_id!("5Xu6iNDMf17wVC6aSoKeQsjF87aTQjrqpA9sX3sA5VJX");
 
@@ -13,14 +15,23 @@ declare_id!("5Xu6iNDMf17wVC6aSoKeQsjF87aTQjrqpA9sX3sA5VJX");
 pub mod cronos_network {
     use super::*;
 
+    pub fn admin_config_update(
+        ctx: Context<AdminConfigUpdate>,
+        settings: ConfigSettings,
+    ) -> Result<()> {
+        admin_config_update::handler(ctx, settings)
+    }
+
     pub fn initialize(
         ctx: Context<Initialize>,
         config_bump: u8,
         pool_bump: u8,
         registry_bump: u8,
         registry_page_bump: u8,
+        rewards_bump: u8,
         snapshot_bump: u8,
         snapshot_page_bump: u8,
+        crank_authority: Pubkey,
     ) -> Result<()> {
         initialize::handler(
             ctx,
@@ -28,8 +39,85 @@ pub mod cronos_network {
             pool_bump,
             registry_bump,
             registry_page_bump,
+            rewards_bump,
             snapshot_bump,
             snapshot_page_bump,
+            crank_authority,
         )
     }
+
+    pub fn node_claim_rewards(ctx: Context<NodeClaimRewards>, entry_id: u64) -> Result<()> {
+        node_claim_rewards::handler(ctx, entry_id)
+    }
+
+    pub fn node_deregister<'info>(
+        ctx: Context<'_, '_, '_, 'info, NodeDeregister<'info>>,
+        registry_page_id: u64,
+    ) -> Result<()> {
+        node_deregister::handler(ctx, registry_page_id)
+    }
+
+    pub fn node_heartbeat(ctx: Context<NodeHeartbeat>) -> Result<()> {
+        node_heartbeat::handler(ctx)
+    }
+
+    pub fn node_register(ctx: Context<NodeRegister>, node_bump: u8) -> Result<()> {
+        node_register::handler(ctx, node_bump)
+    }
+
+    pub fn node_stake(ctx: Context<NodeStake>, amount: u64) -> Result<()> {
+        node_stake::handler(ctx, amount)
+    }
+
+    pub fn node_unstake(ctx: Context<NodeUnstake>, amount: u64) -> Result<()> {
+        node_unstake::handler(ctx, amount)
+    }
+
+    pub fn node_update(ctx: Context<NodeUpdate>, metadata: NodeMetadata) -> Result<()> {
+        node_update::handler(ctx, metadata)
+    }
+
+    pub fn node_withdraw(ctx: Context<NodeWithdraw>) -> Result<()> {
+        node_withdraw::handler(ctx)
+    }
+
+    pub fn pool_commit(ctx: Context<PoolCommit>) -> Result<()> {
+        pool_commit::handler(ctx)
+    }
+
+    pub fn pool_cycle(ctx: Context<PoolCycle>, entry_id: u64) -> Result<()> {
+        pool_cycle::handler(ctx, entry_id)
+    }
+
+    pub fn registry_page_new(ctx: Context<RegistryPageNew>, registry_page_bump: u8) -> Result<()> {
+        registry_page_new::handler(ctx, registry_page_bump)
+    }
+
+    pub fn report_miss<'info>(ctx: Context<'_, '_, '_, 'info, ReportMiss<'info>>) -> Result<()> {
+        report_miss::handler(ctx)
+    }
+
+    pub fn snapshot_capture<'info>(
+        ctx: Context<'_, '_, '_, 'info, SnapshotCapture<'info>>,
+    ) -> Result<()> {
+        snapshot_capture::handler(ctx)
+    }
+
+    pub fn snapshot_new(ctx: Context<SnapshotNew>, snapshot_bump: u8) -> Result<()> {
+        snapshot_new::handler(ctx, snapshot_bump)
+    }
+
+    pub fn snapshot_page_new(ctx: Context<SnapshotPageNew>, snapshot_page_bump: u8) -> Result<()> {
+        snapshot_page_new::handler(ctx, snapshot_page_bump)
+    }
+
+    pub fn snapshot_rotate<'info>(
+        ctx: Context<'_, '_, '_, 'info, SnapshotRotate<'info>>,
+    ) -> Result<()> {
+        snapshot_rotate::handler(ctx)
+    }
+
+    pub fn snapshot_start(ctx: Context<SnapshotStart>) -> Result<()> {
+        snapshot_start::handler(ctx)
+    }
 }
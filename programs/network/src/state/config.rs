@@ -6,6 +6,15 @@ use {
 
 pub const SEED_CONFIG: &[u8] = b"config";
 
+const DEFAULT_HEARTBEAT_WINDOW: i64 = 0; // Disabled, so nodes are sampled whether or not they heartbeat
+const DEFAULT_MISS_WINDOW: i64 = 10; // Seconds a delegate has to execute a due task
+const DEFAULT_SLASH_RATE: u64 = 100; // 1%
+const DEFAULT_SLASH_THRESHOLD: u64 = 10;
+const DEFAULT_UNBONDING_PERIOD: i64 = 60 * 60 * 24 * 7; // 7 days
+
+/// Slash rates are in basis points of the node's tokens.
+pub const SLASH_RATE_DENOMINATOR: u64 = 10_000;
+
 /**
  * Config
  */
@@ -15,7 +24,13 @@ pub const SEED_CONFIG: &[u8] = b"config";
 pub struct Config {
     pub admin: Pubkey,
     pub bump: u8,
-    pub pool_size: usize,
+    pub crank_authority: Pubkey,
+    pub heartbeat_window: i64,
+    pub miss_window: i64,
+    pub pool_size: u64,
+    pub slash_rate: u64,
+    pub slash_threshold: u64,
+    pub unbonding_period: i64,
 }
 
 impl Config {
@@ -38,7 +53,13 @@ impl TryFrom<Vec<u8>> for Config {
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct ConfigSettings {
     pub admin: Pubkey,
-    pub pool_size: usize,
+    pub crank_authority: Pubkey,
+    pub heartbeat_window: i64,
+    pub miss_window: i64,
+    pub pool_size: u64,
+    pub slash_rate: u64,
+    pub slash_threshold: u64,
+    pub unbonding_period: i64,
 }
 
 /**
@@ -46,24 +67,42 @@ pub struct ConfigSettings {
  */
 
 pub trait ConfigAccount {
-    fn new(&mut self, admin: Pubkey, bump: u8) -> Result<()>;
+    fn new(&mut self, admin: Pubkey, bump: u8, crank_authority: Pubkey) -> Result<()>;
 
     fn update(&mut self, admin: &Signer, settings: ConfigSettings) -> Result<()>;
 }
 
 impl ConfigAccount for Account<'_, Config> {
-    fn new(&mut self, admin: Pubkey, bump: u8) -> Result<()> {
+    fn new(&mut self, admin: Pubkey, bump: u8, crank_authority: Pubkey) -> Result<()> {
         require!(self.bump == 0, CronosError::AccountAlreadyInitialized);
         self.admin = admin;
         self.bump = bump;
+        self.crank_authority = crank_authority;
+        self.heartbeat_window = DEFAULT_HEARTBEAT_WINDOW;
+        self.miss_window = DEFAULT_MISS_WINDOW;
         self.pool_size = 1; 
+        self.slash_rate = DEFAULT_SLASH_RATE;
+        self.slash_threshold = DEFAULT_SLASH_THRESHOLD;
+        self.unbonding_period = DEFAULT_UNBONDING_PERIOD;
         Ok(())
     }
 
     fn update(&mut self, admin: &Signer, settings: ConfigSettings) -> Result<()> {
         require!(self.admin == admin.key(), CronosError::AdminAuthorityInvalid);
+        require!(settings.heartbeat_window >= 0, CronosError::HeartbeatWindowInvalid);
+        require!(settings.miss_window >= 0, CronosError::MissWindowInvalid);
+        require!(settings.pool_size > 0, CronosError::PoolSizeInvalid);
+        require!(settings.slash_rate <= SLASH_RATE_DENOMINATOR, CronosError::SlashRateInvalid);
+        require!(settings.slash_threshold > 0, CronosError::SlashThresholdInvalid);
+        require!(settings.unbonding_period >= 0, CronosError::UnbondingPeriodInvalid);
         self.admin = settings.admin;
+        self.crank_authority = settings.crank_authority;
+        self.heartbeat_window = settings.heartbeat_window;
+        self.miss_window = settings.miss_window;
         self.pool_size = settings.pool_size;
+        self.slash_rate = settings.slash_rate;
+        self.slash_threshold = settings.slash_threshold;
+        self.unbonding_period = settings.unbonding_period;
         Ok(())
     }
 }
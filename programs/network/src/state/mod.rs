@@ -3,6 +3,7 @@ mod node;
 mod pool;
 mod registry;
 mod registry_page;
+mod rewards;
 mod snapshot;
 mod snapshot_page;
 
@@ -11,5 +12,6 @@ pub use node::*;
 pub use pool::*;
 pub use registry::*;
 pub use registry_page::*;
+pub use rewards::*;
 pub use snapshot::*;
 pub use snapshot_page::*;
\ No newline at end of file
@@ -1,10 +1,17 @@
 use {
+    super::{Config, SLASH_RATE_DENOMINATOR},
     crate::{errors::CronosError, pda::PDA},
     anchor_lang::{prelude::*, AnchorDeserialize},
+    anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer},
     std::convert::TryFrom,
 };
 
 pub const SEED_NODE: &[u8] = b"node";
+pub const SEED_NODE_TOKENS: &[u8] = b"node_tokens";
+
+const MAX_NAME_LEN: usize = 32;
+const MAX_PLUGIN_VERSION_LEN: usize = 16;
+const MAX_URL_LEN: usize = 128;
 
 /**
  * Node
@@ -15,7 +22,14 @@ pub const SEED_NODE: &[u8] = b"node";
 pub struct Node {
     pub authority: Pubkey,
     pub bump: u8,
+    pub claimable_snapshot_id: u64,
+    pub last_seen_ts: i64,
+    pub metadata: NodeMetadata,
+    pub miss_count: u64,
+    pub miss_ts: i64,
     pub stake: u64,
+    pub unbonding_amount: u64,
+    pub unbonding_ts: i64,
 }
 
 impl Node {
@@ -25,6 +39,13 @@ impl Node {
             authority.as_ref(),
         ], &crate::ID)
     }
+
+    pub fn tokens_pda(node: Pubkey) -> PDA {
+        Pubkey::find_program_address(&[
+            SEED_NODE_TOKENS,
+            node.as_ref(),
+        ], &crate::ID)
+    }
 }
 
 impl TryFrom<Vec<u8>> for Node {
@@ -39,34 +60,299 @@ impl TryFrom<Vec<u8>> for Node {
  * NodeAccount
  */
 
-pub trait NodeAccount {
+pub trait NodeAccount<'info> {
     fn new(&mut self, authority: &mut Signer, bump: u8) -> Result<()>;
 
-    fn stake(&mut self, _amount: u64, _authority: &mut Signer) -> Result<()>;
+    fn close_tokens(
+        &self,
+        authority: &Signer<'info>,
+        authority_tokens: &Account<'info, TokenAccount>,
+        node_tokens: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()>;
+
+    fn heartbeat(&mut self, clock: &Sysvar<Clock>) -> Result<()>;
+
+    fn miss(
+        &mut self,
+        config: &Account<Config>,
+        exec_at: i64,
+        seats: u64,
+        seat_count: u64,
+        mint: &Account<'info, Mint>,
+        node_tokens: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()>;
 
-    fn unstake(&mut self, _amount: u64, _authority: &mut Signer) -> Result<()>;
+    fn stake(
+        &mut self,
+        amount: u64,
+        authority: &Signer<'info>,
+        authority_tokens: &Account<'info, TokenAccount>,
+        node_tokens: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()>;
+
+    fn unstake(&mut self, amount: u64, clock: &Sysvar<Clock>, config: &Account<Config>) -> Result<()>;
+
+    fn update(&mut self, metadata: NodeMetadata) -> Result<()>;
+
+    fn withdraw(
+        &mut self,
+        clock: &Sysvar<Clock>,
+        node_tokens: &Account<'info, TokenAccount>,
+        authority_tokens: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()>;
 }
 
-impl NodeAccount for Account<'_, Node> {
+impl<'info> NodeAccount<'info> for Account<'info, Node> {
     fn new(&mut self, authority: &mut Signer, bump: u8) -> Result<()> {
         require!(self.bump == 0, CronosError::AccountAlreadyInitialized);
         self.authority = authority.key();
         self.bump = bump;
+        self.claimable_snapshot_id = 0;
+        self.last_seen_ts = 0;
+        self.metadata = NodeMetadata::default();
+        self.miss_count = 0;
+        self.miss_ts = 0;
         self.stake = 0;
+        self.unbonding_amount = 0;
+        self.unbonding_ts = 0;
+        Ok(())
+    }
+
+    fn close_tokens(
+        &self,
+        authority: &Signer<'info>,
+        authority_tokens: &Account<'info, TokenAccount>,
+        node_tokens: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()> {
+        let seeds: &[&[u8]] = &[SEED_NODE, self.authority.as_ref(), &[self.bump]];
+
+        // Sweep any tokens sent to the vault outside of staking, so it can be closed
+        if node_tokens.amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: node_tokens.to_account_info(),
+                        to: authority_tokens.to_account_info(),
+                        authority: self.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                node_tokens.amount,
+            )?;
+        }
+
+        // Close the vault and return its rent to the authority
+        token::close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: node_tokens.to_account_info(),
+                destination: authority.to_account_info(),
+                authority: self.to_account_info(),
+            },
+            &[seeds],
+        ))
+    }
+
+    fn heartbeat(&mut self, clock: &Sysvar<Clock>) -> Result<()> {
+        self.last_seen_ts = clock.unix_timestamp;
         Ok(())
     }
 
-    fn stake(&mut self, _amount: u64, _authority: &mut Signer) -> Result<()> {
-        
-        // TODO transfer tokens from authority token account to node token account
+    fn miss(
+        &mut self,
+        config: &Account<Config>,
+        exec_at: i64,
+        seats: u64,
+        seat_count: u64,
+        mint: &Account<'info, Mint>,
+        node_tokens: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()> {
+        // Misses are reported in order of due time, so each is only counted once
+        require!(exec_at > self.miss_ts, CronosError::MissAlreadyReported);
+        self.miss_ts = exec_at;
+        self.miss_count = self.miss_count.checked_add(1).unwrap();
+        if self.miss_count < config.slash_threshold {
+            return Ok(());
+        }
+
+        // Slash the node's share of a fraction of its tokens, including those still unbonding
+        self.miss_count = 0;
+        let stake_slashed = slash_amount(self.stake, config.slash_rate, seats, seat_count);
+        let unbonding_slashed =
+            slash_amount(self.unbonding_amount, config.slash_rate, seats, seat_count);
+        let amount = stake_slashed.checked_add(unbonding_slashed).unwrap();
+        if amount == 0 {
+            return Ok(());
+        }
+        token::burn(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Burn {
+                    mint: mint.to_account_info(),
+                    from: node_tokens.to_account_info(),
+                    authority: self.to_account_info(),
+                },
+                &[&[SEED_NODE, self.authority.as_ref(), &[self.bump]]],
+            ),
+            amount,
+        )?;
+        self.stake = self.stake.checked_sub(stake_slashed).unwrap();
+        self.unbonding_amount = self.unbonding_amount.checked_sub(unbonding_slashed).unwrap();
 
         Ok(())
     }
 
-    fn unstake(&mut self, _amount: u64, _authority: &mut Signer) -> Result<()> {
-        
-        // TODO transfer tokens from node token account to authority token account
+    fn stake(
+        &mut self,
+        amount: u64,
+        authority: &Signer<'info>,
+        authority_tokens: &Account<'info, TokenAccount>,
+        node_tokens: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()> {
+        // Transfer tokens from the authority's token account to the node's vault
+        token::transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                Transfer {
+                    from: authority_tokens.to_account_info(),
+                    to: node_tokens.to_account_info(),
+                    authority: authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        // The new stake is counted by the next snapshot
+        self.stake = self.stake.checked_add(amount).unwrap();
 
         Ok(())
     }
+
+    fn unstake(&mut self, amount: u64, clock: &Sysvar<Clock>, config: &Account<Config>) -> Result<()> {
+        require!(amount <= self.stake, CronosError::StakeInsufficient);
+
+        // Move the stake into unbonding. Each unstake restarts the unbonding period.
+        self.stake = self.stake.checked_sub(amount).unwrap();
+        self.unbonding_amount = self.unbonding_amount.checked_add(amount).unwrap();
+        self.unbonding_ts = clock.unix_timestamp.checked_add(config.unbonding_period).unwrap();
+
+        Ok(())
+    }
+
+    fn update(&mut self, metadata: NodeMetadata) -> Result<()> {
+        metadata.validate()?;
+        self.metadata = metadata;
+        Ok(())
+    }
+
+    fn withdraw(
+        &mut self,
+        clock: &Sysvar<Clock>,
+        node_tokens: &Account<'info, TokenAccount>,
+        authority_tokens: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+    ) -> Result<()> {
+        require!(self.unbonding_amount > 0, CronosError::UnbondingEmpty);
+        require!(clock.unix_timestamp >= self.unbonding_ts, CronosError::UnbondingInProgress);
+
+        // Release the unbonded tokens from the node's vault back to the authority
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: node_tokens.to_account_info(),
+                    to: authority_tokens.to_account_info(),
+                    authority: self.to_account_info(),
+                },
+                &[&[SEED_NODE, self.authority.as_ref(), &[self.bump]]],
+            ),
+            self.unbonding_amount,
+        )?;
+
+        self.unbonding_amount = 0;
+
+        Ok(())
+    }
+}
+
+/**
+ * NodeMetadata
+ */
+
+/// Optional details about who runs a node, shown to users.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, Default, PartialEq)]
+pub struct NodeMetadata {
+    pub name: Option<String>,
+    pub plugin_version: Option<String>,
+    pub url: Option<String>,
+}
+
+impl NodeMetadata {
+    pub fn validate(&self) -> Result<()> {
+        let fits = |value: &Option<String>, max_len: usize| {
+            value.as_ref().map_or(true, |value| value.len() <= max_len)
+        };
+        require!(
+            fits(&self.name, MAX_NAME_LEN)
+                && fits(&self.plugin_version, MAX_PLUGIN_VERSION_LEN)
+                && fits(&self.url, MAX_URL_LEN),
+            CronosError::NodeMetadataTooLong
+        );
+        Ok(())
+    }
+}
+
+/// The amount slashed from `amount` at a rate in basis points, split by the node's share of the
+/// pool's seats and rounded down.
+pub fn slash_amount(amount: u64, rate: u64, seats: u64, seat_count: u64) -> u64 {
+    (amount as u128)
+        .checked_mul(rate as u128)
+        .unwrap()
+        .checked_mul(seats as u128)
+        .unwrap()
+        .checked_div((SLASH_RATE_DENOMINATOR as u128).checked_mul(seat_count as u128).unwrap())
+        .unwrap() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_validate() {
+        assert!(NodeMetadata::default().validate().is_ok());
+        let metadata = NodeMetadata {
+            name: Some("a".repeat(MAX_NAME_LEN)),
+            plugin_version: Some("0.1.8".into()),
+            url: Some("https://cronos.so".into()),
+        };
+        assert!(metadata.validate().is_ok());
+        let metadata = NodeMetadata {
+            url: Some("a".repeat(MAX_URL_LEN + 1)),
+            ..NodeMetadata::default()
+        };
+        assert!(metadata.validate().is_err());
+    }
+
+    #[test]
+    fn test_slash_amount() {
+        assert_eq!(slash_amount(1_000, 0, 1, 1), 0);
+        assert_eq!(slash_amount(1_000, 100, 1, 1), 10);
+        assert_eq!(slash_amount(1_000, SLASH_RATE_DENOMINATOR, 1, 1), 1_000);
+        assert_eq!(slash_amount(99, 100, 1, 1), 0);
+        assert_eq!(slash_amount(u64::MAX, SLASH_RATE_DENOMINATOR, 1, 1), u64::MAX);
+
+        // The slash is split across the pool's seats
+        assert_eq!(slash_amount(1_000, SLASH_RATE_DENOMINATOR, 1, 4), 250);
+        assert_eq!(slash_amount(1_000, SLASH_RATE_DENOMINATOR, 2, 4), 500);
+        assert_eq!(slash_amount(1_000, 100, 1, 3), 3);
+    }
 }
@@ -1,12 +1,18 @@
 use {
-    crate::{errors::CronosError, pda::PDA, state::{SnapshotEntry, SnapshotStatus}},
-    super::{Config, SnapshotPage, Snapshot},
-    anchor_lang::{AnchorDeserialize, prelude::*},
-    std::{collections::{hash_map::DefaultHasher, VecDeque}, convert::TryFrom, hash::{Hasher, Hash}},
+    crate::{errors::{CronosError, PoolError}, pda::PDA, state::{SnapshotEntry, SnapshotStatus}},
+    super::{Config, Node, SnapshotPage, Snapshot},
+    anchor_lang::{AnchorDeserialize, prelude::*, solana_program::{hash::hashv, slot_hashes::MAX_ENTRIES}},
+    std::{cmp::Ordering, collections::VecDeque, convert::TryFrom},
 };
 
 pub const SEED_POOL: &[u8] = b"pool";
 
+/// Size of an entry in the SlotHashes sysvar, a slot followed by its hash.
+const SLOT_HASH_ENTRY_LEN: usize = 8 + 32;
+
+/// How many past delegate sets the pool keeps, so misses can be attributed after it cycles.
+pub const MAX_POOL_EPOCHS: usize = 8;
+
 /**
  * Pool
  */
@@ -15,15 +21,56 @@ pub const SEED_POOL: &[u8] = b"pool";
 #[derive(Debug)]
 pub struct Pool {
     pub bump: u8,
+    pub commit_slot: Option<u64>,
+    pub cycle_ts: i64,
     pub delegates: VecDeque<Pubkey>,
     pub nonce: u64,
     pub snapshot_ts: i64,
+    pub epochs: VecDeque<PoolEpoch>,
 }
 
 impl Pool {
     pub fn pda() -> PDA {
         Pubkey::find_program_address(&[SEED_POOL], &crate::ID)
     }
+
+    /// The seats each delegate held through the whole time from `start_ts` to `end_ts`, in the
+    /// order the delegates first appear in the pool at `start_ts`. None if the pool's history
+    /// doesn't reach back to `start_ts`.
+    pub fn obliged_delegates(&self, start_ts: i64, end_ts: i64) -> Option<Vec<(Pubkey, u64)>> {
+        // The delegate sets which held the pool at some point in the window, oldest first
+        let current = PoolEpoch {
+            delegates: self.delegates.clone(),
+            end_ts: i64::MAX,
+            start_ts: self.cycle_ts,
+        };
+        let epochs: Vec<&PoolEpoch> = self
+            .epochs
+            .iter()
+            .chain(std::iter::once(&current))
+            .filter(|epoch| epoch.start_ts <= end_ts && start_ts < epoch.end_ts)
+            .collect();
+        if epochs.first()?.start_ts > start_ts {
+            return None;
+        }
+
+        // A delegate is obliged for the seats it held in every one of those sets
+        let mut delegates: Vec<(Pubkey, u64)> = vec![];
+        for delegate in epochs[0].delegates.iter() {
+            if delegates.iter().any(|(authority, _)| authority == delegate) {
+                continue;
+            }
+            let seats = epochs
+                .iter()
+                .map(|epoch| epoch.delegates.iter().filter(|d| *d == delegate).count() as u64)
+                .min()
+                .unwrap_or(0);
+            if seats > 0 {
+                delegates.push((*delegate, seats));
+            }
+        }
+        Some(delegates)
+    }
 }
 
 impl TryFrom<Vec<u8>> for Pool {
@@ -33,6 +80,18 @@ impl TryFrom<Vec<u8>> for Pool {
     }
 }
 
+/**
+ * PoolEpoch
+ */
+
+/// The delegates which held the pool from `start_ts` until it cycled at `end_ts`.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq)]
+pub struct PoolEpoch {
+    pub delegates: VecDeque<Pubkey>,
+    pub end_ts: i64,
+    pub start_ts: i64,
+}
+
 /**
  * PoolAccount
  */
@@ -40,11 +99,17 @@ impl TryFrom<Vec<u8>> for Pool {
 pub trait PoolAccount {
     fn new(&mut self, bump: u8) -> Result<()>;
 
+    fn commit(&mut self, clock: &Sysvar<Clock>) -> Result<()>;
+
     fn cycle(
         &mut self, 
+        clock: &Sysvar<Clock>,
         config: &Account<Config>, 
+        node: &AccountInfo,
+        slot_hashes: &[u8],
         snapshot: &Account<Snapshot>,
-        snapshot_page: &Account<SnapshotPage>
+        snapshot_page: &Account<SnapshotPage>,
+        entry_id: u64,
     ) -> Result<()>;
 }
 
@@ -52,54 +117,320 @@ impl PoolAccount for Account<'_, Pool> {
     fn new(&mut self, bump: u8) -> Result<()> {
         require!(self.bump == 0, CronosError::AccountAlreadyInitialized);
         self.bump = bump;
+        self.commit_slot = None;
+        self.cycle_ts = 0;
         self.delegates = VecDeque::new();
+        self.epochs = VecDeque::new();
+        Ok(())
+    }
+
+    fn commit(&mut self, clock: &Sysvar<Clock>) -> Result<()> {
+        // Leave a pending draw to be cycled, unless its slot hash has aged out of the sysvar
+        if self.commit_slot.map_or(false, |slot| !is_commit_expired(slot, clock.slot)) {
+            return Ok(());
+        }
+        self.commit_slot = Some(clock.slot);
         Ok(())
     }
 
     fn cycle(
         &mut self, 
+        clock: &Sysvar<Clock>,
         config: &Account<Config>,
+        node: &AccountInfo,
+        slot_hashes: &[u8],
         snapshot: &Account<Snapshot>,
-        snapshot_page: &Account<SnapshotPage>
+        snapshot_page: &Account<SnapshotPage>,
+        entry_id: u64,
     ) -> Result<()> {
         require!(snapshot.status == SnapshotStatus::Current, CronosError::SnapshotNotCurrent);
-        require!(snapshot_page.entries.len() > 0, CronosError::PageRangeInvalid);
-
-        // Sample the nonce value
-        let sample: u64 = self.nonce.checked_rem(snapshot.cumulative_stake).unwrap();
-
-        // Verify the sample is within the snapshot page range 
-        let first: &SnapshotEntry = snapshot_page.entries.first().unwrap();
-        let last: &SnapshotEntry = snapshot_page.entries.last().unwrap();
-        require!(
-            sample >= first.node_cumulative_stake && 
-            sample <= last.node_cumulative_stake, 
-            CronosError::PageRangeInvalid
-        );
+        require!(snapshot.cumulative_stake > 0, CronosError::SnapshotNoStake);
+
+        // Draw with the hash of the slot the draw was committed in. That slot wasn't produced
+        // yet when the commit was sent, so callers can't wait for a hash which favors them.
+        let commit_slot = self
+            .commit_slot
+            .filter(|slot| *slot < clock.slot)
+            .ok_or(CronosError::PoolCommitMissing)?;
+        let slot_hash =
+            find_slot_hash(slot_hashes, commit_slot).ok_or(CronosError::PoolCommitExpired)?;
+        self.commit_slot = None;
+
+        // Advance the nonce with the committed slot hash and sample it
+        self.nonce = next_nonce(self.nonce, slot_hash);
+        let sample = self.nonce.checked_rem(snapshot.cumulative_stake).unwrap();
 
-        // Pop the last delegate out of the pool
-        self.delegates.pop_front();
+        // The caller finds the sampled entry off chain, so only the page holding it is loaded
+        let entry = snapshot_page
+            .entry(entry_id)
+            .filter(|entry| holds_sample(entry, sample))
+            .ok_or(PoolError::InvalidSnapshotPage)?;
+        require!(node.key() == Node::pda(entry.node_authority).0, PoolError::InvalidNode);
+
+        // Skip nodes which have deregistered since the snapshot was captured, or haven't
+        // heartbeated recently. The nonce has advanced, so the next cycle samples again.
+        if node.data_is_empty() {
+            return Ok(());
+        }
+        let node = Account::<Node>::try_from(node)?;
+        if !is_live(node.last_seen_ts, clock.unix_timestamp, config.heartbeat_window) {
+            return Ok(());
+        }
+
+        // Archive the outgoing delegates, so misses from while they held the pool can still be
+        // attributed to them
+        let epoch = PoolEpoch {
+            delegates: self.delegates.clone(),
+            end_ts: clock.unix_timestamp,
+            start_ts: self.cycle_ts,
+        };
+        self.epochs.push_back(epoch);
+        while self.epochs.len() > MAX_POOL_EPOCHS {
+            self.epochs.pop_front();
+        }
+        self.cycle_ts = clock.unix_timestamp;
 
         // Push the sampled delegate into the pool
-        self.delegates.push_back(
-            snapshot_page.entries.iter().rev().find(
-                |e| e.node_cumulative_stake <= sample
-            )
-            .unwrap()
-            .node_authority
-        );
+        self.delegates.push_back(entry.node_authority);
 
         // Drain pool to the configured size limit
-        while self.delegates.len() > config.pool_size {
+        while self.delegates.len() as u64 > config.pool_size {
             self.delegates.pop_front();
         }
 
-        // Hash the nonce
-        let mut hasher = DefaultHasher::new();
-        self.nonce.hash(&mut hasher);
-        self.nonce = hasher.finish();
-
         Ok(())
     }
 }
 
+/// The next nonce, from a sha256 hash of the current nonce and a slot hash.
+pub fn next_nonce(nonce: u64, slot_hash: &[u8]) -> u64 {
+    let hash = hashv(&[&nonce.to_le_bytes(), slot_hash]);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.to_bytes()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// The hash of a slot in the SlotHashes sysvar's data, if the slot is recent enough to be held.
+///
+/// The data is the entry count followed by the entries, most recent slot first.
+pub fn find_slot_hash(slot_hashes: &[u8], slot: u64) -> Option<&[u8]> {
+    let len = u64::from_le_bytes(slot_hashes.get(..8)?.try_into().ok()?) as usize;
+    let entries = slot_hashes.get(8..8 + len.checked_mul(SLOT_HASH_ENTRY_LEN)?)?;
+    let (mut lo, mut hi) = (0, len);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let entry = &entries[mid * SLOT_HASH_ENTRY_LEN..(mid + 1) * SLOT_HASH_ENTRY_LEN];
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        match entry_slot.cmp(&slot) {
+            Ordering::Equal => return Some(&entry[8..]),
+            Ordering::Greater => lo = mid + 1,
+            Ordering::Less => hi = mid,
+        }
+    }
+    None
+}
+
+/// Whether a committed slot is too old for its hash to still be in the SlotHashes sysvar.
+pub fn is_commit_expired(commit_slot: u64, slot: u64) -> bool {
+    slot > commit_slot.saturating_add(MAX_ENTRIES as u64)
+}
+
+/// The entry whose stake range holds the sample.
+///
+/// Each entry covers the range from the previous entry's cumulative stake up to its own, so
+/// nodes are sampled in proportion to their stake and nodes without stake are never sampled.
+pub fn find_entry(entries: &[SnapshotEntry], sample: u64) -> Option<&SnapshotEntry> {
+    entries.iter().find(|e| sample < e.node_cumulative_stake)
+}
+
+/// Whether the sample falls in the entry's stake range.
+pub fn holds_sample(entry: &SnapshotEntry, sample: u64) -> bool {
+    entry.node_cumulative_stake.saturating_sub(entry.node_stake) <= sample
+        && sample < entry.node_cumulative_stake
+}
+
+/// Whether a node last seen at `last_seen_ts` heartbeated within the window. A window of zero
+/// disables the check.
+pub fn is_live(last_seen_ts: i64, now: i64, heartbeat_window: i64) -> bool {
+    heartbeat_window == 0 || now <= last_seen_ts.saturating_add(heartbeat_window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(stakes: &[u64]) -> Vec<SnapshotEntry> {
+        let mut cumulative_stake = 0;
+        stakes
+            .iter()
+            .map(|stake| {
+                cumulative_stake += stake;
+                SnapshotEntry {
+                    node_authority: Pubkey::new_unique(),
+                    node_cumulative_stake: cumulative_stake,
+                    node_stake: *stake,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_entry() {
+        let entries = entries(&[2, 0, 3]);
+        assert_eq!(find_entry(&entries, 0).unwrap().node_authority, entries[0].node_authority);
+        assert_eq!(find_entry(&entries, 1).unwrap().node_authority, entries[0].node_authority);
+        assert_eq!(find_entry(&entries, 2).unwrap().node_authority, entries[2].node_authority);
+        assert_eq!(find_entry(&entries, 4).unwrap().node_authority, entries[2].node_authority);
+        assert!(find_entry(&entries, 5).is_none());
+    }
+
+    #[test]
+    fn test_holds_sample_matches_find_entry() {
+        let entries = entries(&[2, 0, 3]);
+        for sample in 0..5 {
+            let holders = entries
+                .iter()
+                .filter(|entry| holds_sample(entry, sample))
+                .map(|entry| entry.node_authority)
+                .collect::<Vec<Pubkey>>();
+            assert_eq!(holders, vec![find_entry(&entries, sample).unwrap().node_authority]);
+        }
+        assert!(!entries.iter().any(|entry| holds_sample(entry, 5)));
+    }
+
+    #[test]
+    fn test_is_live() {
+        assert!(is_live(1_000, 1_060, 60));
+        assert!(!is_live(1_000, 1_061, 60));
+        assert!(is_live(1_000, 1_000_000, 0));
+    }
+
+    #[test]
+    fn test_is_live_after_snapshot() {
+        // The node heartbeated shortly before the snapshot was captured, then went offline.
+        // Liveness is read from the node when the pool cycles, not from the snapshot.
+        let (last_seen_ts, snapshot_ts, cycle_ts) = (1_000, 1_030, 1_200);
+        assert!(is_live(last_seen_ts, snapshot_ts, 60));
+        assert!(!is_live(last_seen_ts, cycle_ts, 60));
+
+        // Heartbeating again after the snapshot makes it live again
+        assert!(is_live(1_150, cycle_ts, 60));
+    }
+
+    fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for slot in slots {
+            data.extend(slot.to_le_bytes());
+            data.extend([*slot as u8; 32]);
+        }
+        data
+    }
+
+    #[test]
+    fn test_find_slot_hash() {
+        let data = slot_hashes(&[107, 106, 104, 103, 100]);
+        for slot in [107, 106, 104, 103, 100] {
+            assert_eq!(find_slot_hash(&data, slot), Some(&[slot as u8; 32][..]));
+        }
+        for slot in [108, 105, 101, 99] {
+            assert_eq!(find_slot_hash(&data, slot), None);
+        }
+        assert_eq!(find_slot_hash(&slot_hashes(&[]), 100), None);
+        assert_eq!(find_slot_hash(&data[..data.len() - 1], 100), None);
+    }
+
+    #[test]
+    fn test_is_commit_expired() {
+        assert!(!is_commit_expired(100, 101));
+        assert!(!is_commit_expired(100, 100 + MAX_ENTRIES as u64));
+        assert!(is_commit_expired(100, 101 + MAX_ENTRIES as u64));
+    }
+
+    #[test]
+    fn test_next_nonce_is_stable() {
+        let slot_hash = [7u8; 32];
+        assert_eq!(next_nonce(1, &slot_hash), next_nonce(1, &slot_hash));
+        assert_ne!(next_nonce(1, &slot_hash), next_nonce(2, &slot_hash));
+        assert_ne!(next_nonce(1, &slot_hash), next_nonce(1, &[8u8; 32]));
+    }
+
+    fn epoch(delegates: &[Pubkey], start_ts: i64, end_ts: i64) -> PoolEpoch {
+        PoolEpoch {
+            delegates: delegates.iter().copied().collect(),
+            end_ts,
+            start_ts,
+        }
+    }
+
+    #[test]
+    fn test_obliged_delegates() {
+        let (a, b, c, d) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let pool = Pool {
+            bump: 0,
+            commit_slot: None,
+            cycle_ts: 200,
+            delegates: VecDeque::from(vec![c, d]),
+            nonce: 0,
+            snapshot_ts: 0,
+            epochs: VecDeque::from(vec![epoch(&[a, b], 50, 100), epoch(&[b, c], 100, 200)]),
+        };
+
+        // Delegates are obliged for the seats they held through the whole window
+        assert_eq!(pool.obliged_delegates(60, 80), Some(vec![(a, 1), (b, 1)]));
+        assert_eq!(pool.obliged_delegates(90, 150), Some(vec![(b, 1)]));
+        assert_eq!(pool.obliged_delegates(150, 250), Some(vec![(c, 1)]));
+        assert_eq!(pool.obliged_delegates(250, 260), Some(vec![(c, 1), (d, 1)]));
+        assert_eq!(pool.obliged_delegates(90, 210), Some(vec![]));
+
+        // The window starts before the pool's history
+        assert_eq!(pool.obliged_delegates(40, 60), None);
+    }
+
+    #[test]
+    fn test_obliged_delegates_counts_seats() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let pool = Pool {
+            bump: 0,
+            commit_slot: None,
+            cycle_ts: 100,
+            delegates: VecDeque::from(vec![a, b, b]),
+            nonce: 0,
+            snapshot_ts: 0,
+            epochs: VecDeque::from(vec![epoch(&[a, a, b], 0, 100)]),
+        };
+        assert_eq!(pool.obliged_delegates(10, 20), Some(vec![(a, 2), (b, 1)]));
+        assert_eq!(pool.obliged_delegates(90, 110), Some(vec![(a, 1), (b, 1)]));
+        assert_eq!(pool.obliged_delegates(110, 120), Some(vec![(a, 1), (b, 2)]));
+    }
+
+    #[test]
+    fn test_selection_is_proportional_to_stake() {
+        let stakes = [100, 200, 300, 400];
+        let entries = entries(&stakes);
+        let cumulative_stake: u64 = stakes.iter().sum();
+
+        let draws = 100_000;
+        let mut counts = vec![0u64; stakes.len()];
+        let mut nonce = 0;
+        for slot in 0..draws {
+            nonce = next_nonce(nonce, &hashv(&[&(slot as u64).to_le_bytes()]).to_bytes());
+            let entry = find_entry(&entries, nonce % cumulative_stake).unwrap();
+            let i = entries
+                .iter()
+                .position(|e| e.node_authority == entry.node_authority)
+                .unwrap();
+            counts[i] += 1;
+        }
+
+        // Each node is selected within 1% of its share of the stake
+        for (stake, count) in stakes.iter().zip(counts) {
+            let expected = draws as f64 * *stake as f64 / cumulative_stake as f64;
+            assert!((count as f64 - expected).abs() < draws as f64 * 0.01);
+        }
+    }
+}
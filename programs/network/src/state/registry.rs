@@ -5,7 +5,7 @@ use {
         pda::PDA,
         state::{NodeAccount, SnapshotAccount, SnapshotStatus, PAGE_LIMIT},
     },
-    anchor_lang::{prelude::*, AnchorDeserialize},
+    anchor_lang::{prelude::*, AccountsClose, AnchorDeserialize},
     anchor_spl::token::Mint,
     std::convert::TryFrom,
 };
@@ -63,6 +63,19 @@ pub trait RegistryAccount {
 
     fn new_snapshot(&mut self, snapshot: &mut Account<Snapshot>, snapshot_bump: u8) -> Result<()>;
 
+    fn remove_node(
+        &mut self,
+        node: &Account<Node>,
+        registry_page: &mut Account<RegistryPage>,
+        last_registry_page: Option<&mut Account<RegistryPage>>,
+    ) -> Result<()>;
+
+    fn remove_page<'info>(
+        &mut self,
+        registry_page: &Account<'info, RegistryPage>,
+        sol_destination: AccountInfo<'info>,
+    ) -> Result<()>;
+
     fn rotate_snapshot(
         &mut self,
         clock: &Sysvar<Clock>,
@@ -96,6 +109,7 @@ impl RegistryAccount for Account<'_, Registry> {
     ) -> Result<()> {
         require!(!self.is_locked, CronosError::RegistryLocked);
         node.new(authority, node_bump)?;
+        node.claimable_snapshot_id = self.snapshot_count;
         registry_page.append(node)?;
         self.node_count = self.node_count.checked_add(1).unwrap();
         Ok(())
@@ -114,12 +128,61 @@ impl RegistryAccount for Account<'_, Registry> {
     }
 
     fn new_snapshot(&mut self, snapshot: &mut Account<Snapshot>, snapshot_bump: u8) -> Result<()> {
-        require!(!self.is_locked, CronosError::RegistryLocked);
-        self.lock()?;
+        require!(self.is_locked, CronosError::RegistryMustBeLocked);
         snapshot.new(snapshot_bump, self.snapshot_count)?;
         Ok(())
     }
 
+    fn remove_node(
+        &mut self,
+        node: &Account<Node>,
+        registry_page: &mut Account<RegistryPage>,
+        last_registry_page: Option<&mut Account<RegistryPage>>,
+    ) -> Result<()> {
+        require!(!self.is_locked, CronosError::RegistryLocked);
+        require!(
+            node.stake == 0 && node.unbonding_amount == 0,
+            CronosError::NodeHasStake
+        );
+
+        // Rewards the node hasn't claimed are forfeited, and released to later snapshots once
+        // their claim windows close
+
+        // The last page is only passed separately if the node is on an earlier page
+        let last_page_id = self.page_count.checked_sub(1).unwrap();
+        match &last_registry_page {
+            Some(last_registry_page) => require!(
+                last_registry_page.id == last_page_id && registry_page.id < last_page_id,
+                CronosError::PageRangeInvalid
+            ),
+            None => require!(registry_page.id == last_page_id, CronosError::PageRangeInvalid),
+        }
+
+        registry_page.remove(node, last_registry_page)?;
+        self.node_count = self.node_count.checked_sub(1).unwrap();
+        Ok(())
+    }
+
+    fn remove_page<'info>(
+        &mut self,
+        registry_page: &Account<'info, RegistryPage>,
+        sol_destination: AccountInfo<'info>,
+    ) -> Result<()> {
+        require!(!self.is_locked, CronosError::RegistryLocked);
+
+        // Only an empty last page may be removed, and the first page is always kept
+        require!(
+            self.page_count > 1
+                && registry_page.id == self.page_count.checked_sub(1).unwrap()
+                && registry_page.nodes.is_empty(),
+            CronosError::PageRangeInvalid
+        );
+
+        registry_page.close(sol_destination)?;
+        self.page_count = self.page_count.checked_sub(1).unwrap();
+        Ok(())
+    }
+
     fn rotate_snapshot(
         &mut self,
         clock: &Sysvar<Clock>,
@@ -178,6 +241,7 @@ impl RegistryAccount for Account<'_, Registry> {
     }
 
     fn lock(&mut self) -> Result<()> {
+        require!(!self.is_locked, CronosError::RegistryLocked);
         self.is_locked = true;
         Ok(())
     }
@@ -47,6 +47,12 @@ pub trait RegistryPageAccount {
     fn new(&mut self, bump: u8, id: u64) -> Result<()>;
 
     fn append(&mut self, node: &mut Account<Node>) -> Result<()>;
+
+    fn remove(
+        &mut self,
+        node: &Account<Node>,
+        last_page: Option<&mut Account<RegistryPage>>,
+    ) -> Result<()>;
 }
 
 impl RegistryPageAccount for Account<'_, RegistryPage> {
@@ -63,4 +69,102 @@ impl RegistryPageAccount for Account<'_, RegistryPage> {
         self.nodes.push(node.key());
         Ok(())
     }
+
+    fn remove(
+        &mut self,
+        node: &Account<Node>,
+        last_page: Option<&mut Account<RegistryPage>>,
+    ) -> Result<()> {
+        swap_remove(
+            &node.key(),
+            &mut self.nodes,
+            last_page.map(|page| &mut page.nodes),
+        )
+    }
+}
+
+/// Remove the node from a page, filling its slot with the registry's last node.
+///
+/// The last node is popped from `last_nodes`, or from `nodes` itself if this is the last page.
+/// This keeps every page but the last one full, which the snapshot capture offsets rely on.
+pub fn swap_remove(
+    node: &Pubkey,
+    nodes: &mut Vec<Pubkey>,
+    last_nodes: Option<&mut Vec<Pubkey>>,
+) -> Result<()> {
+    let index = nodes
+        .iter()
+        .position(|key| key == node)
+        .ok_or(CronosError::PageRangeInvalid)?;
+    match last_nodes {
+        Some(last_nodes) => {
+            nodes[index] = last_nodes.pop().ok_or(CronosError::PageRangeInvalid)?;
+        }
+        None => {
+            nodes.swap_remove(index);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pages(sizes: &[usize]) -> Vec<Vec<Pubkey>> {
+        sizes
+            .iter()
+            .map(|size| (0..*size).map(|_| Pubkey::new_unique()).collect())
+            .collect()
+    }
+
+    fn remove(pages: &mut [Vec<Pubkey>], page_id: usize, node: &Pubkey) {
+        let last = pages.len() - 1;
+        let (head, tail) = pages.split_at_mut(last);
+        match head.get_mut(page_id) {
+            Some(page) => swap_remove(node, page, Some(&mut tail[0])).unwrap(),
+            None => swap_remove(node, &mut tail[0], None).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_remove_from_first_page() {
+        let mut pages = pages(&[3, 3, 2]);
+        let before = pages.clone();
+        let node = before[0][1];
+        remove(&mut pages, 0, &node);
+        assert_eq!(pages[0], vec![before[0][0], before[2][1], before[0][2]]);
+        assert_eq!(pages.iter().map(|p| p.len()).collect::<Vec<_>>(), vec![3, 3, 1]);
+        assert!(!pages.iter().flatten().any(|key| key == &node));
+    }
+
+    #[test]
+    fn test_remove_from_middle_page() {
+        let mut pages = pages(&[3, 3, 1]);
+        let before = pages.clone();
+        let node = before[1][0];
+        remove(&mut pages, 1, &node);
+        assert_eq!(pages[1], vec![before[2][0], before[1][1], before[1][2]]);
+        assert_eq!(pages.iter().map(|p| p.len()).collect::<Vec<_>>(), vec![3, 3, 0]);
+        assert!(!pages.iter().flatten().any(|key| key == &node));
+    }
+
+    #[test]
+    fn test_remove_from_last_page() {
+        let mut pages = pages(&[3, 3, 3]);
+        let before = pages.clone();
+        let node = before[2][0];
+        remove(&mut pages, 2, &node);
+        assert_eq!(pages[2], vec![before[2][2], before[2][1]]);
+        assert_eq!(pages.iter().map(|p| p.len()).collect::<Vec<_>>(), vec![3, 3, 2]);
+        assert!(!pages.iter().flatten().any(|key| key == &node));
+    }
+
+    #[test]
+    fn test_remove_missing_node() {
+        let mut pages = pages(&[3, 1]);
+        let (head, tail) = pages.split_at_mut(1);
+        assert!(swap_remove(&Pubkey::new_unique(), &mut head[0], Some(&mut tail[0])).is_err());
+        assert_eq!(tail[0].len(), 1);
+    }
 }
@@ -0,0 +1,205 @@
+use {
+    super::{Node, Registry, Snapshot, SnapshotEntry, SnapshotStatus},
+    crate::{errors::CronosError, pda::PDA},
+    anchor_lang::{prelude::*, AnchorDeserialize},
+    std::convert::TryFrom,
+};
+
+pub const SEED_REWARDS: &[u8] = b"rewards";
+
+/// Rewards may be claimed from an archived snapshot until this many newer snapshots have been
+/// archived. What's left unclaimed is then released to the snapshots which follow.
+pub const CLAIM_WINDOW: u64 = 24 * 30; // 30 days of hourly snapshots
+
+/**
+ * Rewards
+ */
+
+/// The vault of fees paid out to stakers. Lamports beyond its rent and the rewards still unclaimed
+/// from archived snapshots are allocated to the current snapshot as it's archived.
+#[account]
+#[derive(Debug)]
+pub struct Rewards {
+    pub bump: u8,
+    pub unclaimed: u64,
+}
+
+impl Rewards {
+    pub fn pda() -> PDA {
+        Pubkey::find_program_address(&[SEED_REWARDS], &crate::ID)
+    }
+}
+
+impl TryFrom<Vec<u8>> for Rewards {
+    type Error = Error;
+    fn try_from(data: Vec<u8>) -> std::result::Result<Self, Self::Error> {
+        Rewards::try_deserialize(&mut data.as_slice())
+    }
+}
+
+/**
+ * RewardsAccount
+ */
+
+pub trait RewardsAccount {
+    fn new(&mut self, bump: u8) -> Result<()>;
+
+    fn allocate(&mut self, snapshot: &mut Account<Snapshot>) -> Result<()>;
+
+    fn claim(
+        &mut self,
+        authority: &mut Signer,
+        node: &mut Account<Node>,
+        registry: &Account<Registry>,
+        snapshot: &mut Account<Snapshot>,
+        entry: &SnapshotEntry,
+    ) -> Result<()>;
+
+    fn release(&mut self, snapshot: &mut Account<Snapshot>) -> Result<()>;
+}
+
+impl RewardsAccount for Account<'_, Rewards> {
+    fn new(&mut self, bump: u8) -> Result<()> {
+        require!(self.bump == 0, CronosError::AccountAlreadyInitialized);
+        self.bump = bump;
+        self.unclaimed = 0;
+        Ok(())
+    }
+
+    fn allocate(&mut self, snapshot: &mut Account<Snapshot>) -> Result<()> {
+        // Without stake, there's nobody to pay. The fees roll over to the next snapshot.
+        if snapshot.cumulative_stake == 0 {
+            return Ok(());
+        }
+
+        // Allocate the fees which arrived since the last allocation
+        let info = self.to_account_info();
+        let rent = Rent::get()?.minimum_balance(info.data_len());
+        let amount = info
+            .lamports()
+            .saturating_sub(rent)
+            .saturating_sub(self.unclaimed);
+        snapshot.rewards = amount;
+        self.unclaimed = self.unclaimed.checked_add(amount).unwrap();
+        Ok(())
+    }
+
+    fn claim(
+        &mut self,
+        authority: &mut Signer,
+        node: &mut Account<Node>,
+        registry: &Account<Registry>,
+        snapshot: &mut Account<Snapshot>,
+        entry: &SnapshotEntry,
+    ) -> Result<()> {
+        // Snapshots are claimed in order, and only once their rewards are final. Snapshots whose
+        // claim window has closed are skipped, since their remainder was released.
+        require!(
+            matches!(snapshot.status, SnapshotStatus::Archived { .. }),
+            CronosError::SnapshotNotArchived
+        );
+        let claimable_snapshot_id = node
+            .claimable_snapshot_id
+            .max(first_claimable_snapshot_id(registry.snapshot_count));
+        require!(
+            snapshot.id >= claimable_snapshot_id,
+            CronosError::RewardsAlreadyClaimed
+        );
+        require!(
+            snapshot.id == claimable_snapshot_id,
+            CronosError::RewardsClaimedOutOfOrder
+        );
+        node.claimable_snapshot_id = snapshot.id.checked_add(1).unwrap();
+
+        // Pay the node's share of the snapshot's rewards
+        require!(
+            entry.node_authority == node.authority,
+            CronosError::NodeNotInSnapshot
+        );
+        let amount = reward_share(snapshot.rewards, entry.node_stake, snapshot.cumulative_stake);
+        **self.to_account_info().try_borrow_mut_lamports()? = self
+            .to_account_info()
+            .lamports()
+            .checked_sub(amount)
+            .unwrap();
+        **authority.to_account_info().try_borrow_mut_lamports()? = authority
+            .to_account_info()
+            .lamports()
+            .checked_add(amount)
+            .unwrap();
+        self.unclaimed = self.unclaimed.checked_sub(amount).unwrap();
+        snapshot.rewards_claimed = snapshot.rewards_claimed.checked_add(amount).unwrap();
+
+        Ok(())
+    }
+
+    fn release(&mut self, snapshot: &mut Account<Snapshot>) -> Result<()> {
+        // What nodes didn't claim in time, and the dust from rounding shares down, is no longer
+        // owed to anyone, so it's allocated again with the next snapshot
+        let remainder = snapshot.rewards.checked_sub(snapshot.rewards_claimed).unwrap();
+        self.unclaimed = self.unclaimed.checked_sub(remainder).unwrap();
+        snapshot.rewards_claimed = snapshot.rewards;
+        Ok(())
+    }
+}
+
+/// The oldest snapshot whose rewards may still be claimed, given the registry's snapshot count.
+pub fn first_claimable_snapshot_id(snapshot_count: u64) -> u64 {
+    // The last snapshot is current, and the claim window covers the snapshots archived before it
+    snapshot_count.saturating_sub(1).saturating_sub(CLAIM_WINDOW)
+}
+
+/// The snapshot whose claim window closes as the snapshot with `snapshot_id` becomes current.
+pub fn expiring_snapshot_id(snapshot_id: u64) -> Option<u64> {
+    snapshot_id.checked_sub(CLAIM_WINDOW)?.checked_sub(1)
+}
+
+/// A stake's pro rata share of a snapshot's rewards, rounded down.
+pub fn reward_share(rewards: u64, stake: u64, cumulative_stake: u64) -> u64 {
+    if cumulative_stake == 0 {
+        return 0;
+    }
+    (rewards as u128)
+        .checked_mul(stake as u128)
+        .unwrap()
+        .checked_div(cumulative_stake as u128)
+        .unwrap() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_window() {
+        // Until enough snapshots have been archived, every snapshot stays claimable
+        assert_eq!(first_claimable_snapshot_id(0), 0);
+        assert_eq!(first_claimable_snapshot_id(CLAIM_WINDOW + 1), 0);
+        assert_eq!(first_claimable_snapshot_id(CLAIM_WINDOW + 2), 1);
+        assert_eq!(expiring_snapshot_id(0), None);
+        assert_eq!(expiring_snapshot_id(CLAIM_WINDOW), None);
+        assert_eq!(expiring_snapshot_id(CLAIM_WINDOW + 1), Some(0));
+
+        // Rotating a snapshot in expires the snapshot just before the first claimable one
+        for snapshot_id in 0..CLAIM_WINDOW * 3 {
+            let first_claimable = first_claimable_snapshot_id(snapshot_id + 1);
+            assert_eq!(expiring_snapshot_id(snapshot_id), first_claimable.checked_sub(1));
+            assert_eq!(snapshot_id - first_claimable, CLAIM_WINDOW.min(snapshot_id));
+        }
+    }
+
+    #[test]
+    fn test_reward_share_is_pro_rata() {
+        let stakes = [100, 200, 300, 400];
+        let total = stakes.iter().sum::<u64>();
+        let shares = stakes
+            .iter()
+            .map(|stake| reward_share(1_000_000, *stake, total))
+            .collect::<Vec<u64>>();
+        assert_eq!(shares, vec![100_000, 200_000, 300_000, 400_000]);
+        assert_eq!(reward_share(10, 1, 3), 3);
+        assert_eq!(reward_share(10, 0, 3), 0);
+        assert_eq!(reward_share(10, 1, 0), 0);
+        assert_eq!(reward_share(u64::MAX, u64::MAX, u64::MAX), u64::MAX);
+    }
+}
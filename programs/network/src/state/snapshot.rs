@@ -24,6 +24,8 @@ pub struct Snapshot {
     pub page_count: u64,
     pub status: SnapshotStatus,
     pub cumulative_stake: u64,
+    pub rewards: u64,
+    pub rewards_claimed: u64,
 }
 
 impl Snapshot {
@@ -68,6 +70,9 @@ impl SnapshotAccount for Account<'_, Snapshot> {
         self.node_count = 0;
         self.page_count = 0;
         self.status = SnapshotStatus::InProgress;
+        self.cumulative_stake = 0;
+        self.rewards = 0;
+        self.rewards_claimed = 0;
         Ok(())
     }
 
@@ -92,8 +97,14 @@ impl SnapshotAccount for Account<'_, Snapshot> {
             CronosError::PageRangeInvalid
         );
 
+        // Validate the previous pages have been fully captured
+        require!(
+            self.node_count == self.page_count.checked_mul(PAGE_LIMIT as u64).unwrap(),
+            CronosError::SnapshotIncomplete
+        );
+
         // Initialize new page
-        page.new(page_bump, registry.page_count)?;
+        page.new(page_bump, self.page_count)?;
 
         // Increment page count
         self.page_count = self.page_count.checked_add(1).unwrap();
@@ -119,18 +130,25 @@ impl SnapshotAccount for Account<'_, Snapshot> {
             CronosError::PageRangeInvalid
         );
 
+        // Every page before this one is full, so the nodes captured from this page so far are
+        // the snapshot's node count past the earlier pages
+        let offset = self
+            .node_count
+            .checked_sub(snapshot_page.id.checked_mul(PAGE_LIMIT as u64).unwrap())
+            .ok_or(CronosError::PageRangeInvalid)? as usize;
+
         // Record the cumulative stake of the node authorities
-        let offset = self.node_count.checked_div(PAGE_LIMIT as u64).unwrap() as usize;
         for i in 0..nodes.len() {
             let node = nodes[i];
             require!(
-                registry_page.nodes[offset + i] == node.key(),
+                registry_page.nodes.get(offset + i) == Some(&node.key()),
                 CronosError::PageRangeInvalid,
             );
             self.cumulative_stake = self.cumulative_stake.checked_add(node.stake).unwrap();
             snapshot_page.entries.push(SnapshotEntry {
                 node_authority: node.authority,
                 node_cumulative_stake: self.cumulative_stake,
+                node_stake: node.stake,
             });
         }
 
@@ -1,5 +1,5 @@
 use {
-    crate::pda::PDA,
+    crate::{pda::PDA, state::PAGE_LIMIT},
     anchor_lang::{AnchorDeserialize, prelude::*},
     std::convert::TryFrom,
 };
@@ -30,6 +30,20 @@ impl SnapshotPage {
             &crate::ID
         )
     }
+
+    /// The id of the page which holds a snapshot's entry.
+    pub fn page_id(entry_id: u64) -> u64 {
+        entry_id.checked_div(PAGE_LIMIT as u64).unwrap()
+    }
+
+    /// A snapshot's entry, if it's held by this page.
+    pub fn entry(&self, entry_id: u64) -> Option<&SnapshotEntry> {
+        if SnapshotPage::page_id(entry_id) != self.id {
+            return None;
+        }
+        self.entries
+            .get(entry_id.checked_rem(PAGE_LIMIT as u64).unwrap() as usize)
+    }
 }
 
 impl TryFrom<Vec<u8>> for SnapshotPage {
@@ -64,5 +78,46 @@ impl SnapshotPageAccount for Account<'_, SnapshotPage> {
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
 pub struct SnapshotEntry {
     pub node_authority: Pubkey,
-    pub node_cumulative_stake: u64
+    pub node_cumulative_stake: u64,
+    pub node_stake: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(id: u64, len: usize) -> SnapshotPage {
+        SnapshotPage {
+            bump: 0,
+            entries: (0..len)
+                .map(|_| SnapshotEntry {
+                    node_authority: Pubkey::new_unique(),
+                    node_cumulative_stake: 0,
+                    node_stake: 0,
+                })
+                .collect(),
+            id,
+        }
+    }
+
+    #[test]
+    fn test_entry_is_on_its_page() {
+        let limit = PAGE_LIMIT as u64;
+        let first_page = page(0, PAGE_LIMIT);
+        let second_page = page(1, 2);
+        assert_eq!(SnapshotPage::page_id(0), 0);
+        assert_eq!(SnapshotPage::page_id(limit - 1), 0);
+        assert_eq!(SnapshotPage::page_id(limit), 1);
+        assert_eq!(
+            first_page.entry(limit - 1).map(|entry| entry.node_authority),
+            first_page.entries.last().map(|entry| entry.node_authority)
+        );
+        assert!(first_page.entry(limit).is_none());
+        assert_eq!(
+            second_page.entry(limit + 1).map(|entry| entry.node_authority),
+            Some(second_page.entries[1].node_authority)
+        );
+        assert!(second_page.entry(1).is_none());
+        assert!(second_page.entry(limit + 2).is_none());
+    }
 }
\ No newline at end of file
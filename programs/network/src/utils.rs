@@ -0,0 +1,26 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction},
+};
+
+/// Grow an account to `space` bytes, topping up its rent from the payer.
+pub fn realloc<'info>(
+    account: AccountInfo<'info>,
+    space: usize,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    if space <= account.data_len() {
+        return Ok(());
+    }
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+    if rent > lamports {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, rent.checked_sub(lamports).unwrap()),
+            &[payer, account.clone(), system_program],
+        )?;
+    }
+    account.realloc(space, false)?;
+    Ok(())
+}
@@ -32,4 +32,13 @@ pub enum CronosError {
 
     #[msg("Unknown error")]
     Unknown,
+
+    #[msg("Only delegates of the network pool may execute this task")]
+    NotAuthorizedDelegate,
+    #[msg("The config account is already up to date")]
+    ConfigAlreadyMigrated,
+    #[msg("The account is not the network pool")]
+    PoolInvalid,
+    #[msg("The delegate grace period must be between zero and one hour")]
+    DelegateGracePeriodInvalid,
 }
@@ -0,0 +1,46 @@
+use {
+    crate::{errors::CronosError, state::*, utils::realloc},
+    anchor_lang::{prelude::*, solana_program::system_program, AccountsExit},
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+pub struct AdminConfigMigrate<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: A config initialized before its latest fields were appended can't be deserialized
+    /// until it's grown to fit them
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AdminConfigMigrate>) -> Result<()> {
+    let admin = &ctx.accounts.admin;
+    let config = &ctx.accounts.config;
+    let system_program = &ctx.accounts.system_program;
+
+    // Grow the config to fit the appended fields, which are zeroed
+    let space = 8 + size_of::<Config>();
+    require!(config.data_len() < space, CronosError::ConfigAlreadyMigrated);
+    realloc(
+        config.to_account_info(),
+        space,
+        admin.to_account_info(),
+        system_program.to_account_info(),
+    )?;
+
+    // Default the appended fields
+    let info = config.to_account_info();
+    let mut config = Account::<Config>::try_from(&info)?;
+    require!(config.admin == admin.key(), CronosError::NotAuthorizedAdmin);
+    config.set_delegate_grace_period(DEFAULT_DELEGATE_GRACE_PERIOD)?;
+    config.exit(&crate::ID)
+}
@@ -0,0 +1,35 @@
+use {
+    crate::{network::*, state::*},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct FeeDistribute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            SEED_FEE, 
+            fee.daemon.as_ref()
+        ],
+        bump = fee.bump,
+    )]
+    pub fee: Account<'info, Fee>,
+
+    /// CHECK: The rewards vault only receives lamports, so only its address and owner are checked
+    #[account(
+        mut,
+        seeds = [SEED_REWARDS],
+        seeds::program = network_program::ID,
+        bump,
+        owner = network_program::ID,
+    )]
+    pub rewards: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<FeeDistribute>) -> Result<()> {
+    let fee = &mut ctx.accounts.fee;
+    let rewards = &ctx.accounts.rewards;
+
+    // Anyone may move collected fees into the network's rewards vault, to be paid to stakers
+    fee.distribute(&rewards.to_account_info())
+}
@@ -1,20 +1,24 @@
+pub mod admin_config_migrate;
 pub mod admin_config_update;
 pub mod admin_fee_collect;
 pub mod admin_task_cancel;
 pub mod admin_task_new;
 pub mod daemon_new;
 pub mod daemon_sign;
+pub mod fee_distribute;
 pub mod initialize;
 pub mod task_cancel;
 pub mod task_exec;
 pub mod task_new;
 
+pub use admin_config_migrate::*;
 pub use admin_config_update::*;
 pub use admin_fee_collect::*;
 pub use admin_task_cancel::*;
 pub use admin_task_new::*;
 pub use daemon_new::*;
 pub use daemon_sign::*;
+pub use fee_distribute::*;
 pub use initialize::*;
 pub use task_cancel::*;
 pub use task_exec::*;
@@ -1,5 +1,5 @@
 use {
-    crate::{state::*, errors::CronosError},
+    crate::{errors::CronosError, network::*, state::*},
     anchor_lang::{prelude::*, solana_program::sysvar},
 };
 
@@ -39,6 +39,14 @@ pub struct TaskExec<'info> {
     )]
     pub fee: Account<'info, Fee>,
 
+    /// CHECK: The pool is owned by the network program and read by its layout, if it exists
+    #[account(
+        seeds = [SEED_POOL],
+        seeds::program = network_program::ID,
+        bump,
+    )]
+    pub pool: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [
@@ -57,8 +65,22 @@ pub fn handler(ctx: Context<TaskExec>) -> Result<()> {
     let bot = &mut ctx.accounts.bot;
     let config = &ctx.accounts.config;
     let daemon = &mut ctx.accounts.daemon;
+    let clock = &ctx.accounts.clock;
     let fee = &mut ctx.accounts.fee;
+    let pool = PoolHeader::try_from_account(&ctx.accounts.pool.to_account_info())?;
     let task = &mut ctx.accounts.task;
 
+    // Only pool delegates may execute tasks, until a task is overdue by the grace period.
+    // A missing or empty pool means the network has no nodes yet, so anyone may execute.
+    // A task whose grace period ends past the end of time is never overdue.
+    let is_delegate = pool.map_or(true, |pool| {
+        pool.delegates.is_empty() || pool.delegates.contains(&bot.key())
+    });
+    let is_overdue = task
+        .exec_at
+        .and_then(|exec_at| exec_at.checked_add(config.delegate_grace_period))
+        .map_or(false, |overdue_at| clock.unix_timestamp >= overdue_at);
+    require!(is_delegate || is_overdue, CronosError::NotAuthorizedDelegate);
+
     task.exec(&ctx.remaining_accounts.iter().as_slice(), bot, config, daemon, fee)
 }
@@ -2,8 +2,10 @@ extern crate chrono;
 extern crate cronos_cron;
 
 pub mod errors;
+pub mod network;
 pub mod pda;
 pub mod state;
+pub mod utils;
 
 mod instructions;
 
@@ -24,6 +26,10 @@ pub mod cronos_scheduler {
         admin_config_update::handler(ctx, settings)
     }
 
+    pub fn admin_config_migrate(ctx: Context<AdminConfigMigrate>) -> Result<()> {
+        admin_config_migrate::handler(ctx)
+    }
+
     pub fn admin_fee_collect(ctx: Context<AdminFeeCollect>) -> Result<()> {
         admin_fee_collect::handler(ctx)
     }
@@ -59,6 +65,10 @@ pub mod cronos_scheduler {
         daemon_sign::handler(ctx, ix)
     }
 
+    pub fn fee_distribute(ctx: Context<FeeDistribute>) -> Result<()> {
+        fee_distribute::handler(ctx)
+    }
+
     pub fn task_cancel(ctx: Context<TaskCancel>) -> Result<()> {
         task_cancel::handler(ctx)
     }
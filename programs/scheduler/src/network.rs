@@ -0,0 +1,44 @@
+use {
+    crate::errors::CronosError,
+    anchor_lang::{prelude::*, solana_program::hash::hash},
+};
+
+// The network program depends on the scheduler to verify missed tasks, so the network's accounts
+// are read by their layout here rather than through the network crate.
+
+pub mod network_program {
+    anchor_lang::declare_id!("5Xu6iNDMf17wVC6aSoKeQsjF87aTQjrqpA9sX3sA5VJX");
+}
+
+pub const SEED_POOL: &[u8] = b"pool";
+pub const SEED_REWARDS: &[u8] = b"rewards";
+
+/**
+ * PoolHeader
+ */
+
+/// The leading fields of the network's pool account.
+#[derive(AnchorDeserialize, Debug)]
+pub struct PoolHeader {
+    pub bump: u8,
+    pub commit_slot: Option<u64>,
+    pub cycle_ts: i64,
+    pub delegates: Vec<Pubkey>,
+}
+
+impl PoolHeader {
+    /// Read the pool, or None if the network hasn't created it yet.
+    pub fn try_from_account(account_info: &AccountInfo) -> Result<Option<Self>> {
+        if account_info.owner != &network_program::ID || account_info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = account_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == hash(b"account:Pool").to_bytes()[..8],
+            CronosError::PoolInvalid
+        );
+        PoolHeader::deserialize(&mut &data[8..])
+            .map(Some)
+            .map_err(|_| CronosError::PoolInvalid.into())
+    }
+}
@@ -6,6 +6,9 @@ use {
 
 pub const SEED_CONFIG: &[u8] = b"config";
 
+pub const DEFAULT_DELEGATE_GRACE_PERIOD: i64 = 10; // Seconds a task may be overdue before any node can execute it
+pub const MAX_DELEGATE_GRACE_PERIOD: i64 = 60 * 60; // 1 hour
+
 /**
  * Config
  */
@@ -18,6 +21,8 @@ pub struct Config {
     pub node_fee: u64,
     pub program_fee: u64,
     pub registry_address: Pubkey,
+    // Fields added after the config was deployed are appended below. See admin_config_migrate.
+    pub delegate_grace_period: i64,
 }
 
 impl Config {
@@ -42,6 +47,7 @@ pub struct ConfigSettings {
     pub admin: Pubkey,
     pub node_fee: u64,
     pub program_fee: u64,
+    pub delegate_grace_period: i64,
 }
 /**
  * ConfigAccount
@@ -50,6 +56,8 @@ pub struct ConfigSettings {
 pub trait ConfigAccount {
     fn new(&mut self, admin: Pubkey, bump: u8) -> Result<()>;
 
+    fn set_delegate_grace_period(&mut self, delegate_grace_period: i64) -> Result<()>;
+
     fn update(&mut self, admin: &Signer, settings: ConfigSettings) -> Result<()>;
 }
 
@@ -59,6 +67,7 @@ impl ConfigAccount for Account<'_, Config> {
         self.bump = bump;
         self.node_fee = 0; // Lamports to pay node per task exec
         self.program_fee = 0; // Lamports to pay to program per task exec
+        self.delegate_grace_period = DEFAULT_DELEGATE_GRACE_PERIOD;
 
         // TODO initialize registry_address
 
@@ -70,6 +79,15 @@ impl ConfigAccount for Account<'_, Config> {
         self.admin = settings.admin;
         self.node_fee = settings.node_fee;
         self.program_fee = settings.program_fee;
+        self.set_delegate_grace_period(settings.delegate_grace_period)
+    }
+
+    fn set_delegate_grace_period(&mut self, delegate_grace_period: i64) -> Result<()> {
+        require!(
+            (0..=MAX_DELEGATE_GRACE_PERIOD).contains(&delegate_grace_period),
+            CronosError::DelegateGracePeriodInvalid
+        );
+        self.delegate_grace_period = delegate_grace_period;
         Ok(())
     }
 }
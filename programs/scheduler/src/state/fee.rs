@@ -39,6 +39,8 @@ pub trait FeeAccount {
     fn new(&mut self, daemon: Pubkey, bump: u8) -> Result<()>;
 
     fn collect(&mut self, to: &mut Signer) -> Result<()>;
+
+    fn distribute(&mut self, rewards: &AccountInfo) -> Result<()>;
 }
 
 impl FeeAccount for Account<'_, Fee> {
@@ -65,4 +67,21 @@ impl FeeAccount for Account<'_, Fee> {
 
         Ok(())
     }
+
+    fn distribute(&mut self, rewards: &AccountInfo) -> Result<()> {
+        **self.to_account_info().try_borrow_mut_lamports()? = self
+            .to_account_info()
+            .lamports()
+            .checked_sub(self.balance)
+            .unwrap();
+        **rewards.to_account_info().try_borrow_mut_lamports()? = rewards
+            .to_account_info()
+            .lamports()
+            .checked_add(self.balance)
+            .unwrap();
+
+        self.balance = 0;
+
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 // pub mod clock;
 pub mod heartbeat;
+pub mod network;
 pub mod scheduler;
 
 pub use cronos_scheduler::errors;
@@ -11,4 +12,5 @@ pub use cronos_scheduler::pda;
 
 // Program IDs
 pub use cronos_heartbeat::ID as HEARTBEAT_PROGRAM_ID;
+pub use cronos_network::ID as NETWORK_PROGRAM_ID;
 pub use cronos_scheduler::ID as SCHEDULER_PROGRAM_ID;
@@ -0,0 +1,21 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    cronos_network::state::ConfigSettings,
+};
+
+pub fn admin_config_update(admin: Pubkey, config: Pubkey, settings: ConfigSettings) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new(config, false),
+        ],
+        data: cronos_network::instruction::AdminConfigUpdate { settings }.data(),
+    }
+}
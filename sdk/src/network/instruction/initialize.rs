@@ -0,0 +1,52 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program, sysvar,
+        },
+        InstructionData,
+    },
+    cronos_network::state::{
+        Config, Pool, Registry, RegistryPage, Rewards, Snapshot, SnapshotPage,
+    },
+};
+
+pub fn initialize(admin: Pubkey, crank_authority: Pubkey, mint: Pubkey) -> Instruction {
+    let config_pda = Config::pda();
+    let pool_pda = Pool::pda();
+    let registry_pda = Registry::pda();
+    let registry_page_pda = RegistryPage::pda(0);
+    let rewards_pda = Rewards::pda();
+    let snapshot_pda = Snapshot::pda(0);
+    let snapshot_page_pda = SnapshotPage::pda(snapshot_pda.0, 0);
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new(config_pda.0, false),
+            AccountMeta::new(mint, true),
+            AccountMeta::new(pool_pda.0, false),
+            AccountMeta::new(registry_pda.0, false),
+            AccountMeta::new(registry_page_pda.0, false),
+            AccountMeta::new(rewards_pda.0, false),
+            AccountMeta::new(snapshot_pda.0, false),
+            AccountMeta::new(snapshot_page_pda.0, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: cronos_network::instruction::Initialize {
+            config_bump: config_pda.1,
+            pool_bump: pool_pda.1,
+            registry_bump: registry_pda.1,
+            registry_page_bump: registry_page_pda.1,
+            rewards_bump: rewards_pda.1,
+            snapshot_bump: snapshot_pda.1,
+            snapshot_page_bump: snapshot_page_pda.1,
+            crank_authority,
+        }
+        .data(),
+    }
+}
@@ -0,0 +1,39 @@
+mod admin_config_update;
+mod initialize;
+mod node_claim_rewards;
+mod node_deregister;
+mod node_heartbeat;
+mod node_register;
+mod node_stake;
+mod node_unstake;
+mod node_update;
+mod node_withdraw;
+mod pool_commit;
+mod pool_cycle;
+mod registry_page_new;
+mod report_miss;
+mod snapshot_capture;
+mod snapshot_new;
+mod snapshot_page_new;
+mod snapshot_rotate;
+mod snapshot_start;
+
+pub use admin_config_update::*;
+pub use initialize::*;
+pub use node_claim_rewards::*;
+pub use node_deregister::*;
+pub use node_heartbeat::*;
+pub use node_register::*;
+pub use node_stake::*;
+pub use node_unstake::*;
+pub use node_update::*;
+pub use node_withdraw::*;
+pub use pool_commit::*;
+pub use pool_cycle::*;
+pub use registry_page_new::*;
+pub use report_miss::*;
+pub use snapshot_capture::*;
+pub use snapshot_new::*;
+pub use snapshot_page_new::*;
+pub use snapshot_rotate::*;
+pub use snapshot_start::*;
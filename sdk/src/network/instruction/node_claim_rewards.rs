@@ -0,0 +1,37 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    cronos_network::state::SnapshotPage,
+};
+
+/// Claim a node's share of an archived snapshot's rewards.
+///
+/// `entry_id` is the position of the node's entry in the snapshot. Only the snapshot page which
+/// holds it is passed.
+pub fn node_claim_rewards(
+    authority: Pubkey,
+    entry_id: u64,
+    node: Pubkey,
+    registry: Pubkey,
+    rewards: Pubkey,
+    snapshot: Pubkey,
+) -> Instruction {
+    let snapshot_page = SnapshotPage::pda(snapshot, SnapshotPage::page_id(entry_id)).0;
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(node, false),
+            AccountMeta::new_readonly(registry, false),
+            AccountMeta::new(rewards, false),
+            AccountMeta::new(snapshot, false),
+            AccountMeta::new_readonly(snapshot_page, false),
+        ],
+        data: cronos_network::instruction::NodeClaimRewards { entry_id }.data(),
+    }
+}
@@ -0,0 +1,42 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    cronos_network::state::RegistryPage,
+};
+
+/// Deregister a node, given the id of the registry page which holds it.
+///
+/// If that isn't the registry's last page, `last_registry_page` must be the last page, whose
+/// last node is moved into the deregistered node's slot.
+pub fn node_deregister(
+    authority: Pubkey,
+    authority_tokens: Pubkey,
+    node: Pubkey,
+    registry: Pubkey,
+    registry_page_id: u64,
+    last_registry_page: Option<Pubkey>,
+    tokens: Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(authority, true),
+        AccountMeta::new(authority_tokens, false),
+        AccountMeta::new(node, false),
+        AccountMeta::new(registry, false),
+        AccountMeta::new(RegistryPage::pda(registry_page_id).0, false),
+        AccountMeta::new(tokens, false),
+        AccountMeta::new_readonly(anchor_spl::token::ID, false),
+    ];
+    if let Some(last_registry_page) = last_registry_page {
+        accounts.push(AccountMeta::new(last_registry_page, false));
+    }
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts,
+        data: cronos_network::instruction::NodeDeregister { registry_page_id }.data(),
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+    InstructionData,
+};
+
+pub fn node_heartbeat(authority: Pubkey, node: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new(node, false),
+        ],
+        data: cronos_network::instruction::NodeHeartbeat {}.data(),
+    }
+}
@@ -0,0 +1,39 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program, sysvar,
+        },
+        InstructionData,
+    },
+    cronos_network::pda::PDA,
+};
+
+pub fn node_register(
+    authority: Pubkey,
+    mint: Pubkey,
+    node_pda: PDA,
+    registry: Pubkey,
+    registry_page: Pubkey,
+    tokens: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(node_pda.0, false),
+            AccountMeta::new(registry, false),
+            AccountMeta::new(registry_page, false),
+            AccountMeta::new(tokens, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: cronos_network::instruction::NodeRegister {
+            node_bump: node_pda.1,
+        }
+        .data(),
+    }
+}
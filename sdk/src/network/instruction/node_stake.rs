@@ -0,0 +1,29 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    InstructionData,
+};
+
+pub fn node_stake(
+    amount: u64,
+    authority: Pubkey,
+    authority_tokens: Pubkey,
+    node: Pubkey,
+    registry: Pubkey,
+    tokens: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(authority_tokens, false),
+            AccountMeta::new(node, false),
+            AccountMeta::new_readonly(registry, false),
+            AccountMeta::new(tokens, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: cronos_network::instruction::NodeStake { amount }.data(),
+    }
+}
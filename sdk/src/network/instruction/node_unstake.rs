@@ -0,0 +1,28 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+    InstructionData,
+};
+
+pub fn node_unstake(
+    amount: u64,
+    authority: Pubkey,
+    config: Pubkey,
+    node: Pubkey,
+    registry: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(node, false),
+            AccountMeta::new_readonly(registry, false),
+        ],
+        data: cronos_network::instruction::NodeUnstake { amount }.data(),
+    }
+}
@@ -0,0 +1,23 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program,
+        },
+        InstructionData,
+    },
+    cronos_network::state::NodeMetadata,
+};
+
+pub fn node_update(authority: Pubkey, node: Pubkey, metadata: NodeMetadata) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(node, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: cronos_network::instruction::NodeUpdate { metadata }.data(),
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+    InstructionData,
+};
+
+pub fn node_withdraw(
+    authority: Pubkey,
+    authority_tokens: Pubkey,
+    node: Pubkey,
+    tokens: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(authority_tokens, false),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new(node, false),
+            AccountMeta::new(tokens, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: cronos_network::instruction::NodeWithdraw {}.data(),
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+    InstructionData,
+};
+
+pub fn pool_commit(authority: Pubkey, config: Pubkey, pool: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(pool, false),
+        ],
+        data: cronos_network::instruction::PoolCommit {}.data(),
+    }
+}
@@ -0,0 +1,43 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program, sysvar,
+        },
+        InstructionData,
+    },
+    cronos_network::state::SnapshotPage,
+};
+
+/// Draw a delegate into the pool from the current snapshot.
+///
+/// `entry_id` is the position of the sampled entry in the snapshot, and `node` is the sampled
+/// node's account.
+pub fn pool_cycle(
+    entry_id: u64,
+    payer: Pubkey,
+    config: Pubkey,
+    node: Pubkey,
+    pool: Pubkey,
+    registry: Pubkey,
+    snapshot: Pubkey,
+) -> Instruction {
+    let snapshot_page = SnapshotPage::pda(snapshot, SnapshotPage::page_id(entry_id)).0;
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(node, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(registry, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            AccountMeta::new_readonly(snapshot, false),
+            AccountMeta::new_readonly(snapshot_page, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: cronos_network::instruction::PoolCycle { entry_id }.data(),
+    }
+}
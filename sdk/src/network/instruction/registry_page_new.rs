@@ -0,0 +1,27 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program,
+        },
+        InstructionData,
+    },
+    cronos_network::pda::PDA,
+};
+
+pub fn registry_page_new(payer: Pubkey, registry: Pubkey, registry_page_pda: PDA) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(registry, false),
+            AccountMeta::new(registry_page_pda.0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: cronos_network::instruction::RegistryPageNew {
+            registry_page_bump: registry_page_pda.1,
+        }
+        .data(),
+    }
+}
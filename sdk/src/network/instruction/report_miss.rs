@@ -0,0 +1,45 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+    InstructionData,
+};
+
+/// Report that the pool delegates missed a task's execution window.
+///
+/// The report must be followed by the scheduler's task_exec for the same task in the same
+/// transaction, which proves the task could have been executed. Only tasks of the network's
+/// crank authority can be missed. The node and token vault of each delegate returned by
+/// `Pool::obliged_delegates` for the task's execution window are passed as `delegates`, in order.
+pub fn report_miss(
+    config: Pubkey,
+    delegates: Vec<(Pubkey, Pubkey)>,
+    mint: Pubkey,
+    pool: Pubkey,
+    registry: Pubkey,
+    scheduler_config: Pubkey,
+    task: Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+        AccountMeta::new_readonly(config, false),
+        AccountMeta::new_readonly(sysvar::instructions::ID, false),
+        AccountMeta::new(mint, false),
+        AccountMeta::new_readonly(pool, false),
+        AccountMeta::new_readonly(registry, false),
+        AccountMeta::new_readonly(scheduler_config, false),
+        AccountMeta::new_readonly(task, false),
+        AccountMeta::new_readonly(anchor_spl::token::ID, false),
+    ];
+    for (node, tokens) in delegates {
+        accounts.push(AccountMeta::new(node, false));
+        accounts.push(AccountMeta::new(tokens, false));
+    }
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts,
+        data: cronos_network::instruction::ReportMiss {}.data(),
+    }
+}
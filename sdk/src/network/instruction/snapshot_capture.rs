@@ -0,0 +1,36 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    InstructionData,
+};
+
+pub fn snapshot_capture(
+    payer: Pubkey,
+    registry: Pubkey,
+    registry_page: Pubkey,
+    snapshot: Pubkey,
+    snapshot_page: Pubkey,
+    nodes: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(registry, false),
+        AccountMeta::new_readonly(registry_page, false),
+        AccountMeta::new(snapshot, false),
+        AccountMeta::new(snapshot_page, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(
+        nodes
+            .iter()
+            .map(|node| AccountMeta::new_readonly(*node, false)),
+    );
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts,
+        data: cronos_network::instruction::SnapshotCapture {}.data(),
+    }
+}
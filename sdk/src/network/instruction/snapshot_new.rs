@@ -0,0 +1,27 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program,
+        },
+        InstructionData,
+    },
+    cronos_network::pda::PDA,
+};
+
+pub fn snapshot_new(payer: Pubkey, registry: Pubkey, snapshot_pda: PDA) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(registry, false),
+            AccountMeta::new(snapshot_pda.0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: cronos_network::instruction::SnapshotNew {
+            snapshot_bump: snapshot_pda.1,
+        }
+        .data(),
+    }
+}
@@ -0,0 +1,33 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program,
+        },
+        InstructionData,
+    },
+    cronos_network::pda::PDA,
+};
+
+pub fn snapshot_page_new(
+    payer: Pubkey,
+    registry: Pubkey,
+    snapshot: Pubkey,
+    snapshot_page_pda: PDA,
+) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(registry, false),
+            AccountMeta::new(snapshot, false),
+            AccountMeta::new(snapshot_page_pda.0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: cronos_network::instruction::SnapshotPageNew {
+            snapshot_page_bump: snapshot_page_pda.1,
+        }
+        .data(),
+    }
+}
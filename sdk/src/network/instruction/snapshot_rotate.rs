@@ -0,0 +1,36 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+    InstructionData,
+};
+
+/// Make the next snapshot current and archive the current one.
+///
+/// `expiring_snapshot` is the snapshot whose claim window closes with this rotation, once there
+/// is one.
+pub fn snapshot_rotate(
+    current_snapshot: Pubkey,
+    expiring_snapshot: Option<Pubkey>,
+    next_snapshot: Pubkey,
+    registry: Pubkey,
+    rewards: Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(sysvar::clock::ID, false),
+        AccountMeta::new(current_snapshot, false),
+        AccountMeta::new(next_snapshot, false),
+        AccountMeta::new(registry, false),
+        AccountMeta::new(rewards, false),
+    ];
+    if let Some(expiring_snapshot) = expiring_snapshot {
+        accounts.push(AccountMeta::new(expiring_snapshot, false));
+    }
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts,
+        data: cronos_network::instruction::SnapshotRotate {}.data(),
+    }
+}
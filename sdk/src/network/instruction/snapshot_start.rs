@@ -0,0 +1,19 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    InstructionData,
+};
+
+pub fn snapshot_start(authority: Pubkey, config: Pubkey, registry: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cronos_network::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(registry, false),
+        ],
+        data: cronos_network::instruction::SnapshotStart {}.data(),
+    }
+}
@@ -0,0 +1,4 @@
+pub mod instruction;
+pub mod snapshot;
+
+pub use cronos_network::state;
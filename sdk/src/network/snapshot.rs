@@ -0,0 +1,99 @@
+use {
+    super::{
+        instruction::{snapshot_capture, snapshot_new, snapshot_page_new, snapshot_rotate},
+        state::{
+            expiring_snapshot_id, holds_sample, Registry, RegistryPage, Rewards, Snapshot,
+            SnapshotEntry, SnapshotPage, PAGE_LIMIT,
+        },
+    },
+    anchor_lang::solana_program::{instruction::Instruction, pubkey::Pubkey},
+};
+
+/// Maximum number of nodes captured by one instruction, to keep each transaction within size limits.
+pub const CAPTURE_BATCH_SIZE: usize = 20;
+
+/// Build the transactions which capture a snapshot of the locked registry and rotate it in.
+///
+/// `registry_pages` must hold every page of the registry, in order. `snapshot` is the snapshot
+/// in progress if it was already opened, in which case the steps it has completed are skipped.
+/// The returned transactions must be confirmed one after another, since each depends on the last.
+pub fn snapshot_transactions(
+    payer: Pubkey,
+    registry: &Registry,
+    registry_pages: &[RegistryPage],
+    snapshot: Option<&Snapshot>,
+) -> Vec<Vec<Instruction>> {
+    let registry_pubkey = Registry::pda().0;
+    let current_snapshot_pubkey = Snapshot::pda(registry.snapshot_count.saturating_sub(1)).0;
+    let snapshot_pda = Snapshot::pda(registry.snapshot_count);
+    let snapshot_pubkey = snapshot_pda.0;
+
+    // Open the next snapshot
+    let mut txs = vec![];
+    let (page_count, node_count) = match snapshot {
+        Some(snapshot) => (snapshot.page_count, snapshot.node_count),
+        None => {
+            txs.push(vec![snapshot_new(payer, registry_pubkey, snapshot_pda)]);
+            (0, 0)
+        }
+    };
+
+    // Capture the registry page by page, skipping the nodes which were already captured
+    for registry_page in registry_pages {
+        let registry_page_pubkey = RegistryPage::pda(registry_page.id).0;
+        let snapshot_page_pda = SnapshotPage::pda(snapshot_pubkey, registry_page.id);
+        let captured = if registry_page.id < page_count {
+            node_count.saturating_sub(registry_page.id * PAGE_LIMIT as u64) as usize
+        } else {
+            txs.push(vec![snapshot_page_new(
+                payer,
+                registry_pubkey,
+                snapshot_pubkey,
+                snapshot_page_pda,
+            )]);
+            0
+        };
+        let uncaptured = registry_page.nodes.get(captured..).unwrap_or_default();
+        for nodes in uncaptured.chunks(CAPTURE_BATCH_SIZE) {
+            txs.push(vec![snapshot_capture(
+                payer,
+                registry_pubkey,
+                registry_page_pubkey,
+                snapshot_pubkey,
+                snapshot_page_pda.0,
+                nodes,
+            )]);
+        }
+    }
+
+    // Make the new snapshot current and unlock the registry, closing the claim window of the
+    // snapshot which has aged out of it
+    txs.push(vec![snapshot_rotate(
+        current_snapshot_pubkey,
+        expiring_snapshot_id(registry.snapshot_count).map(|id| Snapshot::pda(id).0),
+        snapshot_pubkey,
+        registry_pubkey,
+        Rewards::pda().0,
+    )]);
+
+    txs
+}
+
+/// The id and entry of the snapshot entry whose stake range holds the sample.
+///
+/// `snapshot_pages` must hold every page of the snapshot, in order.
+pub fn find_snapshot_entry(
+    snapshot_pages: &[SnapshotPage],
+    sample: u64,
+) -> Option<(u64, &SnapshotEntry)> {
+    snapshot_pages.iter().find_map(|page| {
+        let index = page
+            .entries
+            .iter()
+            .position(|entry| holds_sample(entry, sample))?;
+        Some((
+            page.id * PAGE_LIMIT as u64 + index as u64,
+            &page.entries[index],
+        ))
+    })
+}
@@ -0,0 +1,20 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    InstructionData,
+};
+
+pub fn admin_config_migrate(admin: Pubkey, config: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cronos_scheduler::ID,
+        accounts: vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: cronos_scheduler::instruction::AdminConfigMigrate {}.data(),
+    }
+}
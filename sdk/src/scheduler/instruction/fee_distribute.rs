@@ -0,0 +1,18 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    InstructionData,
+};
+
+pub fn fee_distribute(fee: Pubkey, rewards: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cronos_scheduler::ID,
+        accounts: vec![
+            AccountMeta::new(fee, false),
+            AccountMeta::new(rewards, false),
+        ],
+        data: cronos_scheduler::instruction::FeeDistribute {}.data(),
+    }
+}
@@ -1,3 +1,4 @@
+mod admin_config_migrate;
 mod admin_config_update;
 mod admin_fee_collect;
 mod admin_initialize;
@@ -5,10 +6,12 @@ mod admin_task_cancel;
 mod admin_task_new;
 mod daemon_new;
 mod daemon_sign;
+mod fee_distribute;
 mod task_cancel;
 mod task_exec;
 mod task_new;
 
+pub use admin_config_migrate::*;
 pub use admin_config_update::*;
 pub use admin_fee_collect::*;
 pub use admin_initialize::*;
@@ -16,6 +19,7 @@ pub use admin_task_cancel::*;
 pub use admin_task_new::*;
 pub use daemon_new::*;
 pub use daemon_sign::*;
+pub use fee_distribute::*;
 pub use task_cancel::*;
 pub use task_exec::*;
 pub use task_new::*;
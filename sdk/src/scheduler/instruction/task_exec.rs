@@ -12,6 +12,7 @@ pub fn task_exec(
     config: Pubkey,
     daemon: Pubkey,
     fee: Pubkey,
+    pool: Pubkey,
     task: Pubkey,
 ) -> Instruction {
     Instruction {
@@ -22,6 +23,7 @@ pub fn task_exec(
             AccountMeta::new_readonly(config, false),
             AccountMeta::new(daemon, false),
             AccountMeta::new(fee, false),
+            AccountMeta::new_readonly(pool, false),
             AccountMeta::new(task, false),
         ],
         data: cronos_scheduler::instruction::TaskExec {}.data(),
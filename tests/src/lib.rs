@@ -58,13 +58,25 @@ impl TryFrom<&JsonAccountMetaData> for AccountMeta {
 mod tests {
     use {
         crate::{errors::TestError, JsonInstructionData},
+        cronos_plugin::{AsyncClient, Confirmation, FeePayers},
         serde_json::json,
+        solana_client::{
+            client_error::ClientErrorKind, nonblocking::rpc_client::RpcClient as AsyncRpcClient,
+        },
         solana_client_helpers::{Client, RpcClient},
         solana_sdk::{
-            commitment_config::CommitmentConfig, instruction::Instruction,
-            native_token::LAMPORTS_PER_SOL, signature::Keypair, transaction::Transaction,
+            commitment_config::CommitmentConfig,
+            instruction::Instruction,
+            native_token::LAMPORTS_PER_SOL,
+            pubkey::Pubkey,
+            signature::{Keypair, Signature},
+            system_instruction,
+            transaction::Transaction,
+        },
+        std::{
+            sync::{atomic::AtomicBool, Arc},
+            time::{Duration, Instant},
         },
-        std::sync::Arc,
     };
 
     fn new_client() -> Arc<Client> {
@@ -105,6 +117,57 @@ mod tests {
         assert_eq!(2 + 2, 4);
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn plugin_async_client_polls_signature_status() {
+        // Fund a payer with the blocking client, then hand its keypair to the plugin's client
+        let client = new_client();
+        let async_client = AsyncClient {
+            client: AsyncRpcClient::new_with_commitment(
+                "http://localhost:8899".into(),
+                CommitmentConfig::confirmed(),
+            ),
+            identity: Keypair::from_bytes(&client.payer.to_bytes()).unwrap(),
+            fee_payers: FeePayers::new(
+                vec![Keypair::from_bytes(&client.payer.to_bytes()).unwrap()],
+                0,
+            ),
+            prioritization_fees_supported: AtomicBool::new(true),
+        };
+        let payer = async_client.identity_pubkey();
+
+        // Sending returns a signature before the transaction is confirmed
+        let ix = system_instruction::transfer(&payer, &Pubkey::new_unique(), LAMPORTS_PER_SOL / 10);
+        let sig = async_client.sign_and_send(&[ix], "transfer").await.unwrap();
+        let confirmation = async_client
+            .confirm(&sig, Duration::from_secs(30), || false)
+            .await
+            .unwrap();
+        assert_eq!(confirmation, Confirmation::Confirmed);
+
+        // Failed transactions land without preflight and surface their error when polled
+        let ix = system_instruction::transfer(&payer, &Pubkey::new_unique(), 10 * LAMPORTS_PER_SOL);
+        let sig = async_client
+            .sign_and_send(&[ix], "overdraft")
+            .await
+            .unwrap();
+        let err = async_client
+            .confirm(&sig, Duration::from_secs(30), || false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.kind(), ClientErrorKind::TransactionError(_)));
+
+        // Polling stops once the submission is cancelled. The signature is never sent,
+        // so it can't be confirmed before the cancellation is seen.
+        let start = Instant::now();
+        let confirmation = async_client
+            .confirm(&Signature::default(), Duration::from_secs(30), || true)
+            .await
+            .unwrap();
+        assert_eq!(confirmation, Confirmation::Cancelled);
+        assert!(start.elapsed() < Duration::from_secs(30));
+    }
+
     #[test]
     #[ignore]
     fn plugin_bench_single_task() {